@@ -0,0 +1,170 @@
+//! School API Client
+//! Thin HTTP client for the configured school API
+
+use reqwest::{RequestBuilder, StatusCode};
+use serde::{de::DeserializeOwned, Serialize};
+use std::time::Duration;
+
+use crate::common::{ApiResponse, AppError, AppResult, ErrorCode, ErrorKind};
+use crate::config::{load_config, ApiConfig};
+
+/// HTTP client bound to the school API base URL
+#[derive(Clone)]
+pub struct ApiClient {
+    http: reqwest::Client,
+    base_url: String,
+    access_token: Option<String>,
+}
+
+impl ApiClient {
+    pub fn new(config: &ApiConfig) -> AppResult<Self> {
+        let base_url = config.base_url.trim().trim_end_matches('/').to_string();
+        if base_url.is_empty() {
+            return Err(AppError::network("School API is not configured")
+                .with_code(ErrorCode::ApiNotConfigured)
+                .with_retryable(false));
+        }
+
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()
+            .map_err(|e| {
+                AppError::network("Failed to create HTTP client")
+                    .with_code(ErrorCode::ApiUnreachable)
+                    .with_source(e)
+            })?;
+
+        Ok(Self {
+            http,
+            base_url,
+            access_token: None,
+        })
+    }
+
+    /// Client for the API configured in `config.yaml`
+    pub fn from_app(app: &tauri::AppHandle) -> AppResult<Self> {
+        Self::new(&load_config(app).api)
+    }
+
+    /// Send requests with a bearer token
+    pub fn with_token(mut self, access_token: Option<String>) -> Self {
+        self.access_token = access_token;
+        self
+    }
+
+    pub async fn get<T: DeserializeOwned>(&self, path: &str) -> AppResult<T> {
+        self.send(self.http.get(self.url(path))).await
+    }
+
+    pub async fn post<B: Serialize, T: DeserializeOwned>(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> AppResult<T> {
+        self.send(self.http.post(self.url(path)).json(body)).await
+    }
+
+    /// POST where the response carries no data
+    pub async fn post_empty<B: Serialize>(&self, path: &str, body: &B) -> AppResult<()> {
+        self.send_empty(self.http.post(self.url(path)).json(body))
+            .await
+    }
+
+    pub async fn delete(&self, path: &str) -> AppResult<()> {
+        self.send_empty(self.http.delete(self.url(path))).await
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}/{}", self.base_url, path.trim_start_matches('/'))
+    }
+
+    async fn send<T: DeserializeOwned>(&self, request: RequestBuilder) -> AppResult<T> {
+        self.execute(request).await?.data.ok_or_else(|| {
+            AppError::network("School API returned no data")
+                .with_code(ErrorCode::ApiInvalidResponse)
+        })
+    }
+
+    async fn send_empty(&self, request: RequestBuilder) -> AppResult<()> {
+        self.execute::<serde_json::Value>(request).await.map(|_| ())
+    }
+
+    /// Send a request and unwrap the `ApiResponse` envelope
+    async fn execute<T: DeserializeOwned>(
+        &self,
+        request: RequestBuilder,
+    ) -> AppResult<ApiResponse<T>> {
+        let request = match &self.access_token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        };
+
+        let response = request.send().await.map_err(|e| {
+            AppError::network("Could not reach the school API")
+                .with_code(ErrorCode::ApiUnreachable)
+                .with_source(e)
+        })?;
+
+        let status = response.status();
+        let body = response.text().await.map_err(|e| {
+            AppError::network("Failed to read school API response")
+                .with_code(ErrorCode::ApiUnreachable)
+                .with_source(e)
+        })?;
+
+        if !status.is_success() {
+            return Err(status_error(status, &body));
+        }
+
+        // Some endpoints answer 204 / empty bodies
+        if body.trim().is_empty() {
+            return Ok(ApiResponse {
+                success: true,
+                data: None,
+                error: None,
+                message: None,
+            });
+        }
+
+        let envelope: ApiResponse<T> = serde_json::from_str(&body).map_err(|e| {
+            AppError::new(ErrorKind::Serialization, "Unexpected school API response")
+                .with_code(ErrorCode::ApiInvalidResponse)
+                .with_source(e)
+        })?;
+
+        if !envelope.success {
+            let message = envelope
+                .error
+                .or(envelope.message)
+                .unwrap_or_else(|| "School API request failed".to_string());
+            return Err(AppError::invalid_input(message).with_code(ErrorCode::ApiRequestRejected));
+        }
+
+        Ok(envelope)
+    }
+}
+
+/// Map an HTTP error status to an app error, using the server's message when present
+fn status_error(status: StatusCode, body: &str) -> AppError {
+    let server_message = serde_json::from_str::<serde_json::Value>(body)
+        .ok()
+        .and_then(|v| {
+            v.get("message")
+                .or_else(|| v.get("error"))
+                .and_then(|m| m.as_str())
+                .map(str::to_string)
+        });
+    let message = server_message.unwrap_or_else(|| format!("School API returned {}", status));
+
+    let (kind, code) = match status {
+        StatusCode::UNAUTHORIZED => (ErrorKind::Auth, ErrorCode::ApiUnauthorized),
+        StatusCode::FORBIDDEN => (ErrorKind::Permission, ErrorCode::ApiForbidden),
+        StatusCode::NOT_FOUND => (ErrorKind::NotFound, ErrorCode::ApiNotFound),
+        s if s.is_client_error() => (ErrorKind::InvalidInput, ErrorCode::ApiRequestRejected),
+        _ => (ErrorKind::Network, ErrorCode::ApiServerError),
+    };
+
+    AppError::new(kind, message)
+        .with_code(code)
+        .with_details(format!("HTTP {}", status.as_u16()))
+}
@@ -0,0 +1,9 @@
+//! API Module
+//! HTTP access to the school API configured in `AppConfig.api`
+
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+mod client;
+
+pub use client::*;
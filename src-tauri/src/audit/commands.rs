@@ -0,0 +1,49 @@
+//! Audit Commands
+//! Tauri commands for querying the session audit trail
+
+use super::log::{load_audit_entries, query_entries, record_audit_event, verify_audit_entries};
+use super::types::{AuditEntry, AuditEvent, AuditEventKind, AuditQuery, AuditVerification};
+use crate::auth::SessionState;
+use crate::common::{AppResult, PaginatedResponse};
+
+/// Query the audit log, newest first (admin only)
+#[tauri::command]
+pub fn query_audit_log(
+    app: tauri::AppHandle,
+    session: tauri::State<'_, SessionState>,
+    query: Option<AuditQuery>,
+) -> AppResult<PaginatedResponse<AuditEntry>> {
+    session.require_admin()?;
+    let entries = load_audit_entries(&app)?;
+    Ok(query_entries(entries, &query.unwrap_or_default()))
+}
+
+/// Check the audit log's hash chain and keychain anchor for tampering (admin only)
+#[tauri::command]
+pub fn verify_audit_log(
+    app: tauri::AppHandle,
+    session: tauri::State<'_, SessionState>,
+) -> AppResult<AuditVerification> {
+    session.require_admin()?;
+    verify_audit_entries(&app)
+}
+
+/// Record a card tap from the NFC reader
+/// Only the last four characters of the card id are logged
+#[tauri::command]
+pub fn report_card_tap(
+    app: tauri::AppHandle,
+    session: tauri::State<'_, SessionState>,
+    card_uid: String,
+) {
+    let card_uid = card_uid.trim();
+    let suffix: String = card_uid
+        .chars()
+        .skip(card_uid.chars().count().saturating_sub(4))
+        .collect();
+
+    let event = AuditEvent::new(AuditEventKind::CardTap)
+        .with_optional_user(session.current_user().as_ref())
+        .with_details(format!("card …{}", suffix));
+    record_audit_event(&app, event);
+}
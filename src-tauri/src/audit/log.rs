@@ -0,0 +1,359 @@
+//! Audit Log
+//! Append-only JSON-lines log where each entry is hash-chained to the one before it
+
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+use tauri::Manager;
+
+use super::types::{AuditEntry, AuditEvent, AuditQuery, AuditVerification};
+use crate::common::{
+    app_data_file, keychain_get, keychain_set, AppError, AppResult, ErrorKind, PaginatedResponse,
+};
+
+/// Audit log file name
+const AUDIT_FILE_NAME: &str = "audit.jsonl";
+
+/// Keychain entry anchoring the chain head, so truncating or rewriting the file shows up
+const AUDIT_HEAD_KEY: &str = "audit-head";
+
+/// `prev_hash` of the first entry
+const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+const DEFAULT_PAGE_SIZE: u32 = 50;
+const MAX_PAGE_SIZE: u32 = 200;
+
+/// Sequence number and hash of the last entry written
+#[derive(Debug, Clone)]
+struct ChainHead {
+    seq: u64,
+    hash: String,
+}
+
+impl ChainHead {
+    fn of(entry: &AuditEntry) -> Self {
+        Self {
+            seq: entry.seq,
+            hash: entry.hash.clone(),
+        }
+    }
+
+    /// Keychain value, "seq:hash"
+    fn encode(&self) -> String {
+        format!("{}:{}", self.seq, self.hash)
+    }
+
+    fn decode(value: &str) -> Option<Self> {
+        let (seq, hash) = value.split_once(':')?;
+        Some(Self {
+            seq: seq.parse().ok()?,
+            hash: hash.to_string(),
+        })
+    }
+
+    /// Whether `entries` still contain this head unchanged
+    fn anchors(&self, entries: &[AuditEntry]) -> bool {
+        entries
+            .iter()
+            .any(|entry| entry.seq == self.seq && entry.hash == self.hash)
+    }
+}
+
+/// Managed state serializing appends to the audit log
+#[derive(Default)]
+pub struct AuditLog {
+    head: Mutex<Option<ChainHead>>,
+}
+
+impl AuditLog {
+    /// Append an event, chaining it to the last entry on disk
+    pub fn append(&self, app: &tauri::AppHandle, event: AuditEvent) -> AppResult<AuditEntry> {
+        let path = app_data_file(app, AUDIT_FILE_NAME)?;
+        let mut head = self.head.lock().unwrap();
+
+        if head.is_none() {
+            let entries = read_entries(&path)?;
+            let anchor = load_anchor(app).unwrap_or_else(|e| {
+                log::warn!("Audit log anchor unavailable: {}", e);
+                None
+            });
+            *head = match anchor {
+                // Chain on from the anchor so a truncated or rewritten log stays broken
+                Some(anchor) if !anchor.anchors(&entries) => Some(anchor),
+                _ => entries.last().map(ChainHead::of),
+            };
+        }
+
+        let (seq, prev_hash) = match head.as_ref() {
+            Some(head) => (head.seq + 1, head.hash.as_str()),
+            None => (1, GENESIS_HASH),
+        };
+        let entry = chain_entry(seq, prev_hash, event, Utc::now());
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let line = serde_json::to_string(&entry).map_err(|e| {
+            AppError::new(ErrorKind::Serialization, "Failed to serialize audit entry")
+                .with_source(e)
+        })?;
+        let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+        writeln!(file, "{}", line)?;
+
+        let new_head = ChainHead::of(&entry);
+        if let Err(e) = keychain_set(app, AUDIT_HEAD_KEY, &new_head.encode()) {
+            log::warn!("Failed to anchor audit log head: {}", e);
+        }
+        *head = Some(new_head);
+        Ok(entry)
+    }
+}
+
+/// Head recorded in the keychain by the last append, if any
+fn load_anchor(app: &tauri::AppHandle) -> AppResult<Option<ChainHead>> {
+    Ok(keychain_get(app, AUDIT_HEAD_KEY)?.and_then(|value| ChainHead::decode(&value)))
+}
+
+/// Record an audit event
+///
+/// Audit failures never block sign-in, locking or logout, so errors are dropped here.
+pub fn record_audit_event(app: &tauri::AppHandle, event: AuditEvent) {
+    if let Err(e) = app.state::<AuditLog>().append(app, event) {
+        log::error!("Failed to append audit event: {}", e);
+    }
+}
+
+/// Build the next entry in the chain
+pub fn chain_entry(
+    seq: u64,
+    prev_hash: &str,
+    event: AuditEvent,
+    timestamp: DateTime<Utc>,
+) -> AuditEntry {
+    let mut entry = AuditEntry {
+        seq,
+        timestamp,
+        kind: event.kind,
+        user_id: event.user_id,
+        username: event.username,
+        details: event.details,
+        prev_hash: prev_hash.to_string(),
+        hash: String::new(),
+    };
+    entry.hash = entry_hash(&entry);
+    entry
+}
+
+/// SHA-256 over every field except `hash`
+fn entry_hash(entry: &AuditEntry) -> String {
+    let fields = serde_json::json!([
+        entry.seq,
+        entry.timestamp,
+        entry.kind,
+        entry.user_id,
+        entry.username,
+        entry.details,
+        entry.prev_hash,
+    ]);
+    hex::encode(Sha256::digest(fields.to_string().as_bytes()))
+}
+
+/// Check that every entry matches its hash and links to the one before it
+pub fn verify_chain(entries: &[AuditEntry]) -> AuditVerification {
+    let mut prev_hash = GENESIS_HASH;
+    let mut expected_seq = 1;
+
+    let broken_at = entries
+        .iter()
+        .find(|entry| {
+            let intact = entry.seq == expected_seq
+                && entry.prev_hash == prev_hash
+                && entry.hash == entry_hash(entry);
+            prev_hash = &entry.hash;
+            expected_seq += 1;
+            !intact
+        })
+        .map(|entry| entry.seq);
+
+    AuditVerification {
+        valid: broken_at.is_none(),
+        entries: entries.len() as u64,
+        broken_at,
+    }
+}
+
+/// Fail verification if the anchored head is missing from the log or was changed
+///
+/// Entries after the anchor are fine: the keychain write may have failed for them.
+fn check_anchor(verification: &mut AuditVerification, entries: &[AuditEntry], anchor: &ChainHead) {
+    if anchor.anchors(entries) {
+        return;
+    }
+    verification.valid = false;
+    verification.broken_at = Some(
+        verification
+            .broken_at
+            .map_or(anchor.seq, |seq| seq.min(anchor.seq)),
+    );
+}
+
+/// Verify the audit log of this install against its hash chain and keychain anchor
+pub fn verify_audit_entries(app: &tauri::AppHandle) -> AppResult<AuditVerification> {
+    let entries = load_audit_entries(app)?;
+    let mut verification = verify_chain(&entries);
+    if let Some(anchor) = load_anchor(app)? {
+        check_anchor(&mut verification, &entries, &anchor);
+    }
+    Ok(verification)
+}
+
+/// Filter entries newest first and return the requested page
+pub fn query_entries(
+    entries: Vec<AuditEntry>,
+    query: &AuditQuery,
+) -> PaginatedResponse<AuditEntry> {
+    let page = query.page.unwrap_or(1).max(1);
+    let page_size = query
+        .page_size
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE);
+
+    let matching: Vec<AuditEntry> = entries
+        .into_iter()
+        .rev()
+        .filter(|entry| query.matches(entry))
+        .collect();
+    let total = matching.len() as u64;
+    let data = matching
+        .into_iter()
+        .skip(((page - 1) * page_size) as usize)
+        .take(page_size as usize)
+        .collect();
+
+    PaginatedResponse::new(data, total, page, page_size)
+}
+
+/// Read all entries from a log file
+///
+/// Lines that fail to parse are skipped; the chain check then reports the gap.
+pub fn read_entries(path: &Path) -> AppResult<Vec<AuditEntry>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    Ok(fs::read_to_string(path)?
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// Read the audit log of this install
+pub fn load_audit_entries(app: &tauri::AppHandle) -> AppResult<Vec<AuditEntry>> {
+    read_entries(&app_data_file(app, AUDIT_FILE_NAME)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::AuditEventKind;
+    use chrono::Duration;
+
+    fn sample_chain() -> Vec<AuditEntry> {
+        let start = Utc::now();
+        let events = [
+            AuditEvent::new(AuditEventKind::LoginFailed).with_username("teacher01"),
+            AuditEvent::new(AuditEventKind::Login).with_username("teacher01"),
+            AuditEvent::new(AuditEventKind::Lock).with_username("teacher01"),
+            AuditEvent::new(AuditEventKind::Login).with_username("student01"),
+        ];
+
+        let mut entries: Vec<AuditEntry> = Vec::new();
+        for (i, event) in events.into_iter().enumerate() {
+            let prev_hash = entries
+                .last()
+                .map_or(GENESIS_HASH.to_string(), |e| e.hash.clone());
+            entries.push(chain_entry(
+                i as u64 + 1,
+                &prev_hash,
+                event,
+                start + Duration::minutes(i as i64),
+            ));
+        }
+        entries
+    }
+
+    #[test]
+    fn test_chain_verifies_and_detects_tampering() {
+        let mut entries = sample_chain();
+        assert!(verify_chain(&entries).valid);
+
+        entries[1].username = Some("student01".to_string());
+        let result = verify_chain(&entries);
+        assert!(!result.valid);
+        assert_eq!(result.broken_at, Some(2));
+
+        // Dropping an entry breaks the link of the next one
+        let mut entries = sample_chain();
+        entries.remove(2);
+        assert_eq!(verify_chain(&entries).broken_at, Some(4));
+    }
+
+    #[test]
+    fn test_anchor_detects_truncation_and_rewrite() {
+        let entries = sample_chain();
+        let anchor = ChainHead::of(&entries[3]);
+
+        let mut intact = verify_chain(&entries);
+        check_anchor(&mut intact, &entries, &anchor);
+        assert!(intact.valid);
+
+        // Dropping the newest entries leaves a valid chain, but not the anchored head
+        let truncated = &entries[..2];
+        let mut result = verify_chain(truncated);
+        assert!(result.valid);
+        check_anchor(&mut result, truncated, &anchor);
+        assert!(!result.valid);
+        assert_eq!(result.broken_at, Some(4));
+
+        // A rebuilt chain no longer matches the anchored hash
+        let mut rewritten = sample_chain();
+        rewritten.truncate(2);
+        let mut result = verify_chain(&rewritten);
+        check_anchor(&mut result, &rewritten, &ChainHead::of(&entries[1]));
+        assert!(!result.valid);
+
+        // An anchor behind the log, e.g. after a failed keychain write, is fine
+        let mut result = verify_chain(&entries);
+        check_anchor(&mut result, &entries, &ChainHead::of(&entries[1]));
+        assert!(result.valid);
+        assert_eq!(
+            ChainHead::decode(&anchor.encode()).unwrap().hash,
+            anchor.hash
+        );
+    }
+
+    #[test]
+    fn test_query_filters_and_paginates_newest_first() {
+        let query = AuditQuery {
+            username: Some("TEACHER01".to_string()),
+            page_size: Some(2),
+            ..Default::default()
+        };
+        let page = query_entries(sample_chain(), &query);
+        assert_eq!(page.total, 3);
+        assert_eq!(page.total_pages, 2);
+        assert_eq!(page.data[0].kind, AuditEventKind::Lock);
+        assert!(page.has_next);
+
+        let query = AuditQuery {
+            kinds: vec![AuditEventKind::Login],
+            ..Default::default()
+        };
+        let page = query_entries(sample_chain(), &query);
+        assert_eq!(page.total, 2);
+        assert_eq!(page.data[0].username.as_deref(), Some("student01"));
+    }
+}
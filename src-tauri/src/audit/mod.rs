@@ -0,0 +1,13 @@
+//! Audit Module
+//! Tamper-evident local audit trail of sign-ins, locks and logouts on this device
+
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+mod commands;
+mod log;
+mod types;
+
+pub use commands::*;
+pub use log::*;
+pub use types::*;
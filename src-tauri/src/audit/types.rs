@@ -0,0 +1,128 @@
+//! Audit Types
+//! Events recorded in the local session audit trail
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::auth::AuthUser;
+
+/// What happened on this device
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum AuditEventKind {
+    Login,
+    LoginFailed,
+    TokenRefresh,
+    Lock,
+    Unlock,
+    UnlockFailed,
+    CardTap,
+    Logout,
+    /// Logged out by the idle timeout
+    SessionTimeout,
+    /// An admin left kiosk mode
+    KioskExit,
+}
+
+/// An event to append to the audit log
+#[derive(Debug, Clone)]
+pub struct AuditEvent {
+    pub kind: AuditEventKind,
+    pub user_id: Option<String>,
+    pub username: Option<String>,
+    pub details: Option<String>,
+}
+
+impl AuditEvent {
+    pub fn new(kind: AuditEventKind) -> Self {
+        Self {
+            kind,
+            user_id: None,
+            username: None,
+            details: None,
+        }
+    }
+
+    pub fn with_user(mut self, user: &AuthUser) -> Self {
+        self.user_id = Some(user.user_id.clone());
+        self.username = Some(user.username.clone());
+        self
+    }
+
+    /// Attach the signed-in user, if any
+    pub fn with_optional_user(self, user: Option<&AuthUser>) -> Self {
+        match user {
+            Some(user) => self.with_user(user),
+            None => self,
+        }
+    }
+
+    /// Record a username that did not resolve to a user (e.g. failed logins)
+    pub fn with_username(mut self, username: impl Into<String>) -> Self {
+        self.username = Some(username.into());
+        self
+    }
+
+    pub fn with_details(mut self, details: impl Into<String>) -> Self {
+        self.details = Some(details.into());
+        self
+    }
+}
+
+/// A stored audit log entry, chained to the previous one by hash
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AuditEntry {
+    pub seq: u64,
+    pub timestamp: DateTime<Utc>,
+    pub kind: AuditEventKind,
+    pub user_id: Option<String>,
+    pub username: Option<String>,
+    pub details: Option<String>,
+    /// Hash of the previous entry (all zeros for the first)
+    pub prev_hash: String,
+    /// SHA-256 over this entry's fields and `prev_hash`
+    pub hash: String,
+}
+
+/// Filters for querying the audit log
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuditQuery {
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    pub user_id: Option<String>,
+    /// Case-insensitive match on the recorded username
+    pub username: Option<String>,
+    /// Only these event kinds (all when empty)
+    #[serde(default)]
+    pub kinds: Vec<AuditEventKind>,
+    pub page: Option<u32>,
+    pub page_size: Option<u32>,
+}
+
+impl AuditQuery {
+    pub fn matches(&self, entry: &AuditEntry) -> bool {
+        self.from.is_none_or(|from| entry.timestamp >= from)
+            && self.to.is_none_or(|to| entry.timestamp <= to)
+            && self
+                .user_id
+                .as_ref()
+                .is_none_or(|id| entry.user_id.as_ref() == Some(id))
+            && self.username.as_ref().is_none_or(|name| {
+                entry
+                    .username
+                    .as_ref()
+                    .is_some_and(|u| u.eq_ignore_ascii_case(name.trim()))
+            })
+            && (self.kinds.is_empty() || self.kinds.contains(&entry.kind))
+    }
+}
+
+/// Result of verifying the hash chain
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditVerification {
+    pub valid: bool,
+    pub entries: u64,
+    /// Sequence number of the first entry that does not match its hash or link,
+    /// or of the anchored head if it is missing from the log
+    pub broken_at: Option<u64>,
+}
@@ -0,0 +1,150 @@
+//! Saved Accounts
+//! Quick-switch account list persisted separately from session tokens
+
+use serde::{Deserialize, Serialize};
+
+use super::types::SavedAccount;
+use crate::common::{app_data_file, read_yaml, write_yaml, AppError, AppResult, ErrorCode};
+
+/// Saved accounts file name
+const SAVED_ACCOUNTS_FILE_NAME: &str = "saved_accounts.yaml";
+
+/// Ordered list of saved accounts as shown on the login window
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SavedAccountStore {
+    pub accounts: Vec<SavedAccount>,
+}
+
+impl SavedAccountStore {
+    /// Add or update an account, evicting least recently used entries over `cap`
+    ///
+    /// New accounts are placed first; existing ones keep their position.
+    /// Returns the evicted accounts.
+    pub fn upsert(&mut self, account: SavedAccount, cap: usize) -> Vec<SavedAccount> {
+        let user_id = account.user_id.clone();
+        match self.accounts.iter_mut().find(|a| a.user_id == user_id) {
+            Some(existing) => *existing = account,
+            None => self.accounts.insert(0, account),
+        }
+
+        let mut evicted = Vec::new();
+        while self.accounts.len() > cap.max(1) {
+            let lru_index = self
+                .accounts
+                .iter()
+                .enumerate()
+                .filter(|(_, a)| a.user_id != user_id)
+                .min_by_key(|(_, a)| a.last_login)
+                .map(|(index, _)| index);
+
+            match lru_index {
+                Some(index) => evicted.push(self.accounts.remove(index)),
+                None => break,
+            }
+        }
+
+        evicted
+    }
+
+    /// Remove an account by user id, returning whether it existed
+    pub fn remove(&mut self, user_id: &str) -> bool {
+        let before = self.accounts.len();
+        self.accounts.retain(|a| a.user_id != user_id);
+        self.accounts.len() != before
+    }
+
+    /// Reorder accounts to match `user_ids`
+    ///
+    /// Accounts not listed keep their relative order after the listed ones.
+    pub fn reorder(&mut self, user_ids: &[String]) -> AppResult<()> {
+        if let Some(unknown) = user_ids
+            .iter()
+            .find(|id| !self.accounts.iter().any(|a| &a.user_id == *id))
+        {
+            return Err(
+                AppError::not_found(format!("Saved account not found: {}", unknown))
+                    .with_code(ErrorCode::SavedAccountNotFound),
+            );
+        }
+
+        let mut remaining = std::mem::take(&mut self.accounts);
+        for id in user_ids {
+            if let Some(index) = remaining.iter().position(|a| &a.user_id == id) {
+                self.accounts.push(remaining.remove(index));
+            }
+        }
+        self.accounts.append(&mut remaining);
+
+        Ok(())
+    }
+}
+
+/// Load saved accounts from disk
+pub fn load_saved_accounts(app: &tauri::AppHandle) -> AppResult<SavedAccountStore> {
+    let path = app_data_file(app, SAVED_ACCOUNTS_FILE_NAME)?;
+    Ok(read_yaml(&path)?.unwrap_or_default())
+}
+
+/// Save accounts to disk
+pub fn save_saved_accounts(app: &tauri::AppHandle, store: &SavedAccountStore) -> AppResult<()> {
+    let path = app_data_file(app, SAVED_ACCOUNTS_FILE_NAME)?;
+    write_yaml(&path, store)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::UserRoleType;
+    use chrono::{TimeZone, Utc};
+
+    fn account(id: &str, last_login_secs: i64) -> SavedAccount {
+        SavedAccount {
+            user_id: id.to_string(),
+            username: id.to_string(),
+            full_name: id.to_uppercase(),
+            avatar_url: None,
+            avatar_cache: None,
+            role_type: UserRoleType::Teacher,
+            last_login: Some(Utc.timestamp_opt(last_login_secs, 0).unwrap()),
+        }
+    }
+
+    fn ids(store: &SavedAccountStore) -> Vec<&str> {
+        store.accounts.iter().map(|a| a.user_id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_upsert_evicts_least_recently_used() {
+        let mut store = SavedAccountStore::default();
+        store.upsert(account("a", 10), 2);
+        store.upsert(account("b", 20), 2);
+        let evicted = store.upsert(account("c", 30), 2);
+
+        assert_eq!(ids(&store), vec!["c", "b"]);
+        assert_eq!(evicted.len(), 1);
+        assert_eq!(evicted[0].user_id, "a");
+    }
+
+    #[test]
+    fn test_upsert_existing_keeps_position() {
+        let mut store = SavedAccountStore::default();
+        store.upsert(account("a", 10), 3);
+        store.upsert(account("b", 20), 3);
+        store.upsert(account("a", 30), 3);
+
+        assert_eq!(ids(&store), vec!["b", "a"]);
+        assert_eq!(store.accounts[1].last_login, account("a", 30).last_login);
+    }
+
+    #[test]
+    fn test_reorder() {
+        let mut store = SavedAccountStore::default();
+        for (id, t) in [("a", 1), ("b", 2), ("c", 3)] {
+            store.upsert(account(id, t), 5);
+        }
+
+        store.reorder(&["a".to_string(), "c".to_string()]).unwrap();
+        assert_eq!(ids(&store), vec!["a", "c", "b"]);
+        assert!(store.reorder(&["missing".to_string()]).is_err());
+    }
+}
@@ -1,34 +1,564 @@
 //! Authentication Commands
 //! Tauri commands for authentication
 
+use chrono::Utc;
+
+use super::accounts::{load_saved_accounts, save_saved_accounts};
+use super::mfa::{
+    check_session_mfa, load_mfa_store, mfa_required, save_mfa_store, MfaRecord, MfaState,
+    MfaStatus, TotpEnrollment,
+};
+use super::password::{check_password, PasswordCheck};
+use super::pin::MAX_PIN_FAILURES;
+use super::session::{remove_stored_session, save_stored_session, ActiveSession, SessionState};
+use super::throttle::LoginThrottleState;
 use super::types::*;
+use crate::api::ApiClient;
+use crate::audit::{record_audit_event, AuditEvent, AuditEventKind};
+use crate::common::{app_data_file, AppError, AppResult, ErrorKind};
+use crate::config::load_config;
+use crate::deep_link::flush_deep_links;
+use crate::device::DeviceState;
+use crate::idle::IdleMonitor;
+use crate::shortcuts::refresh_shortcuts;
+use crate::tray::refresh_tray;
 
 /// Validate access token
 /// Returns true if token is valid, false otherwise
 #[tauri::command]
-pub async fn validate_token(token: String) -> Result<bool, String> {
+pub async fn validate_token(token: String) -> AppResult<bool> {
     // TODO: Implement actual token validation
     // For now, just check if token is not empty
     Ok(!token.is_empty())
 }
 
-/// Get current user from stored session
+/// Sign in against the school API with this device's id and info attached
+/// Throttled per username; rejected credentials count towards backoff and lockout
+#[tauri::command]
+pub async fn login(
+    app: tauri::AppHandle,
+    device: tauri::State<'_, DeviceState>,
+    throttle: tauri::State<'_, LoginThrottleState>,
+    mfa: tauri::State<'_, MfaState>,
+    request: LoginRequest,
+) -> AppResult<LoginResponse> {
+    // Counted as a failure until the API answers, so parallel attempts can't skip the throttle
+    throttle.begin(&app, &request.username)?;
+
+    let username = request.username.clone();
+    let result = send_login(&app, &device, request).await;
+
+    match &result {
+        Ok(response) => {
+            throttle.succeed(&app, &username)?;
+            mfa.note_login(response);
+        }
+        Err(err) if is_rejected_login(err) => {
+            record_audit_event(
+                &app,
+                AuditEvent::new(AuditEventKind::LoginFailed).with_username(username.trim()),
+            );
+        }
+        Err(_) => throttle.cancel(&app, &username)?,
+    }
+    result
+}
+
+/// Post the login request with this device's id and info attached
+async fn send_login(
+    app: &tauri::AppHandle,
+    device: &DeviceState,
+    request: LoginRequest,
+) -> AppResult<LoginResponse> {
+    let request = request.with_device(device.context(app)?);
+    ApiClient::from_app(app)?.post("auth/login", &request).await
+}
+
+/// Whether the school API turned the credentials down
+/// Network, configuration and validation problems do not count against the user
+fn is_rejected_login(err: &AppError) -> bool {
+    err.kind == ErrorKind::Auth
+}
+
+/// Refresh the current session's access token
+/// The refresh token is bound to the device it was issued to
+#[tauri::command]
+pub async fn refresh_access_token(
+    app: tauri::AppHandle,
+    session: tauri::State<'_, SessionState>,
+) -> AppResult<RefreshTokenResponse> {
+    let (refresh_token, device_id) = session.refresh_credentials()?;
+    let request = RefreshTokenRequest {
+        refresh_token,
+        device_id: Some(device_id),
+    };
+
+    let response: RefreshTokenResponse = ApiClient::from_app(&app)?
+        .post("auth/refresh", &request)
+        .await?;
+
+    session.update_tokens(
+        response.access_token.clone(),
+        response.refresh_token.clone(),
+    );
+    save_stored_session(&app, &session)?;
+    record_audit_event(
+        &app,
+        AuditEvent::new(AuditEventKind::TokenRefresh)
+            .with_optional_user(session.current_user().as_ref()),
+    );
+    Ok(response)
+}
+
+/// Start a session for a user who just signed in with `login`
+/// The user and tokens come from that login, never from the caller; the password or
+/// card id is kept only as a salted hash to unlock the session later
+/// Users enrolled in MFA, or whose role requires it, must call `verify_mfa` first
+#[tauri::command]
+pub async fn start_session(
+    app: tauri::AppHandle,
+    session: tauri::State<'_, SessionState>,
+    idle: tauri::State<'_, IdleMonitor>,
+    mfa: tauri::State<'_, MfaState>,
+    device: tauri::State<'_, DeviceState>,
+    request: StartSessionRequest,
+) -> AppResult<()> {
+    let login = mfa.pending_sign_in(&request.user_id).ok_or_else(|| {
+        AuthError::new(
+            AuthErrorKind::TokenInvalid,
+            "Sign in again to start a session",
+        )
+    })?;
+    let user_id = &login.user.user_id;
+    let required = mfa_required(&load_config(&app).auth, &login.user.role_type);
+    let enrolled = load_mfa_store(&app)?.confirmed(user_id).is_some();
+    let passed = (required || enrolled) && mfa.take(user_id);
+    check_session_mfa(required, enrolled, passed)?;
+    mfa.finish_login(user_id);
+
+    let method = if request.card_uid.is_some() {
+        "card"
+    } else {
+        "password"
+    };
+    let event = AuditEvent::new(AuditEventKind::Login)
+        .with_user(&login.user)
+        .with_details(method);
+
+    let device_id = device.identity(&app)?.device_id;
+    session.start(ActiveSession::new(login, request, device_id));
+    record_audit_event(&app, event);
+    refresh_tray(&app);
+    refresh_shortcuts(&app);
+    idle.reset();
+    save_stored_session(&app, &session)?;
+    flush_deep_links(&app);
+    Ok(())
+}
+
+/// Get current user from the active session
 /// Returns None if no session exists
 #[tauri::command]
-pub async fn get_current_user() -> Result<Option<AuthUser>, String> {
-    // TODO: Implement session retrieval
-    // This would typically read from secure storage
-    Ok(None)
+pub async fn get_current_user(
+    session: tauri::State<'_, SessionState>,
+) -> AppResult<Option<AuthUser>> {
+    Ok(session.current_user())
 }
 
 /// Clear current session
 #[tauri::command]
-pub async fn clear_session() -> Result<(), String> {
-    // TODO: Implement session clearing
-    // This would clear tokens and user data from secure storage
+pub async fn clear_session(
+    app: tauri::AppHandle,
+    session: tauri::State<'_, SessionState>,
+) -> AppResult<()> {
+    session.clear();
+    remove_stored_session(&app)?;
+    refresh_tray(&app);
+    refresh_shortcuts(&app);
+    Ok(())
+}
+
+/// Set a device-local PIN for resuming the current session
+/// Requires the password used to sign in
+#[tauri::command]
+pub async fn set_pin(
+    app: tauri::AppHandle,
+    session: tauri::State<'_, SessionState>,
+    password: String,
+    pin: String,
+) -> AppResult<()> {
+    session.set_pin(&password, &pin)?;
+    save_stored_session(&app, &session)?;
+    Ok(())
+}
+
+/// Remove the PIN from the current session
+/// Requires the password used to sign in
+#[tauri::command]
+pub async fn remove_pin(
+    app: tauri::AppHandle,
+    session: tauri::State<'_, SessionState>,
+    password: String,
+) -> AppResult<()> {
+    if session.remove_pin(&password)? {
+        save_stored_session(&app, &session)?;
+    }
+    Ok(())
+}
+
+/// Get whether a PIN is set and how many attempts remain
+#[tauri::command]
+pub async fn get_pin_status(session: tauri::State<'_, SessionState>) -> AppResult<PinStatus> {
+    let pin = session.snapshot().and_then(|s| s.pin);
+    Ok(PinStatus {
+        enabled: pin.is_some(),
+        attempts_remaining: pin
+            .map(|p| MAX_PIN_FAILURES.saturating_sub(p.failures))
+            .unwrap_or(0),
+    })
+}
+
+/// Check a password against the configured policy
+#[tauri::command]
+pub async fn check_password_strength(
+    app: tauri::AppHandle,
+    password: String,
+    username: Option<String>,
+) -> AppResult<PasswordCheck> {
+    check_against_policy(&app, password, username).await
+}
+
+/// Change the signed-in user's password
+#[tauri::command]
+pub async fn change_password(
+    app: tauri::AppHandle,
+    session: tauri::State<'_, SessionState>,
+    current_password: String,
+    new_password: String,
+) -> AppResult<()> {
+    let user = session.require_user()?;
+    let check = check_against_policy(&app, new_password.clone(), Some(user.username)).await?;
+    if !check.valid {
+        return Err(AuthError::weak_password(check.violations).into());
+    }
+
+    let request = ChangePasswordRequest {
+        current_password,
+        new_password,
+    };
+    ApiClient::from_app(&app)?
+        .with_token(session.access_token())
+        .post_empty("auth/change-password", &request)
+        .await?;
+
+    session.replace_password(&request.new_password);
+    Ok(())
+}
+
+/// Ask the school to reset a forgotten password
+#[tauri::command]
+pub async fn request_password_reset(app: tauri::AppHandle, username: String) -> AppResult<()> {
+    let username = username.trim().to_string();
+    if username.is_empty() {
+        return Err(AuthError::new(AuthErrorKind::UserNotFound, "A username is required").into());
+    }
+
+    ApiClient::from_app(&app)?
+        .post_empty("auth/forgot-password", &PasswordResetRequest { username })
+        .await?;
+    Ok(())
+}
+
+/// Set a new password using a reset token
+#[tauri::command]
+pub async fn confirm_password_reset(
+    app: tauri::AppHandle,
+    token: String,
+    new_password: String,
+) -> AppResult<()> {
+    let check = check_against_policy(&app, new_password.clone(), None).await?;
+    if !check.valid {
+        return Err(AuthError::weak_password(check.violations).into());
+    }
+
+    ApiClient::from_app(&app)?
+        .post_empty(
+            "auth/reset-password",
+            &ConfirmPasswordResetRequest {
+                token,
+                new_password,
+            },
+        )
+        .await?;
+    Ok(())
+}
+
+/// Run the policy check on the blocking pool, since the breached list is scanned from disk
+async fn check_against_policy(
+    app: &tauri::AppHandle,
+    password: String,
+    username: Option<String>,
+) -> AppResult<PasswordCheck> {
+    let policy = load_config(app).auth.password_policy;
+    let breached_list = match policy.breached_list_path.trim() {
+        "" => None,
+        path => Some(app_data_file(app, path)?),
+    };
+    let check = tauri::async_runtime::spawn_blocking(move || {
+        check_password(
+            &policy,
+            &password,
+            username.as_deref(),
+            breached_list.as_deref(),
+        )
+    })
+    .await?;
+    Ok(check)
+}
+
+/// List saved accounts in display order
+#[tauri::command]
+pub fn list_saved_accounts(app: tauri::AppHandle) -> AppResult<Vec<SavedAccount>> {
+    Ok(load_saved_accounts(&app)?.accounts)
+}
+
+/// Add or update a saved account after a successful login
+/// Evicts the least recently used accounts once the configured cap is exceeded
+#[tauri::command]
+pub fn add_saved_account(
+    app: tauri::AppHandle,
+    mut account: SavedAccount,
+) -> AppResult<Vec<SavedAccount>> {
+    let cap = load_config(&app).auth.max_saved_accounts;
+    let mut store = load_saved_accounts(&app)?;
+
+    account.last_login = Some(Utc::now());
+    store.upsert(account, cap);
+    save_saved_accounts(&app, &store)?;
+
+    Ok(store.accounts)
+}
+
+/// Remove a saved account by user id
+#[tauri::command]
+pub fn remove_saved_account(
+    app: tauri::AppHandle,
+    user_id: String,
+) -> AppResult<Vec<SavedAccount>> {
+    let mut store = load_saved_accounts(&app)?;
+    if store.remove(&user_id) {
+        save_saved_accounts(&app, &store)?;
+    }
+
+    Ok(store.accounts)
+}
+
+/// Reorder saved accounts; ids not listed keep their relative order at the end
+#[tauri::command]
+pub fn reorder_saved_accounts(
+    app: tauri::AppHandle,
+    user_ids: Vec<String>,
+) -> AppResult<Vec<SavedAccount>> {
+    let mut store = load_saved_accounts(&app)?;
+    store.reorder(&user_ids)?;
+    save_saved_accounts(&app, &store)?;
+
+    Ok(store.accounts)
+}
+
+/// Check whether a login attempt is currently allowed for this username
+/// Fails with a TooManyAttempts or AccountLocked error carrying retry_after_secs
+#[tauri::command]
+pub fn check_login_attempt(
+    app: tauri::AppHandle,
+    throttle: tauri::State<'_, LoginThrottleState>,
+    username: String,
+) -> AppResult<()> {
+    throttle.check(&app, &username)
+}
+
+/// Clear login lockouts for one username, or all when username is omitted
+/// Requires an admin session
+#[tauri::command]
+pub async fn clear_login_lockouts(
+    app: tauri::AppHandle,
+    session: tauri::State<'_, SessionState>,
+    throttle: tauri::State<'_, LoginThrottleState>,
+    username: Option<String>,
+) -> AppResult<()> {
+    session.require_admin()?;
+    throttle.clear(&app, username.as_deref())
+}
+
+/// Verify a TOTP or recovery code during sign-in (online or offline)
+/// A successful check allows `start_session` for this user within a few minutes
+#[tauri::command]
+pub fn verify_mfa(
+    app: tauri::AppHandle,
+    mfa: tauri::State<'_, MfaState>,
+    throttle: tauri::State<'_, LoginThrottleState>,
+    user_id: String,
+    code: String,
+) -> AppResult<()> {
+    // MFA failures share the login throttle under their own key
+    let throttle_key = format!("mfa:{}", user_id);
+    throttle.check(&app, &throttle_key)?;
+
+    let mut store = load_mfa_store(&app)?;
+    let verified = store
+        .users
+        .get_mut(&user_id)
+        .filter(|record| record.confirmed)
+        .is_some_and(|record| record.verify(&code, Utc::now()));
+
+    save_mfa_store(&app, &store)?;
+    throttle.record(&app, &throttle_key, verified)?;
+
+    if !verified {
+        return Err(AuthError::new(
+            AuthErrorKind::InvalidCredentials,
+            "Invalid authenticator or recovery code",
+        )
+        .into());
+    }
+
+    mfa.grant(&user_id);
+    Ok(())
+}
+
+/// User enrolling in MFA: the signed-in user, or `user_id` part-way through signing in
+fn enrolling_user(
+    session: &SessionState,
+    mfa: &MfaState,
+    user_id: Option<&str>,
+) -> Result<AuthUser, AuthError> {
+    match user_id {
+        Some(user_id) => mfa.pending_login(user_id).ok_or_else(|| {
+            AuthError::new(
+                AuthErrorKind::TokenInvalid,
+                "Sign in again to set up an authenticator",
+            )
+        }),
+        None => session.require_user(),
+    }
+}
+
+/// Start TOTP enrollment for the signed-in user, or for `user_id` right after `login`
+/// Returns the secret, otpauth URI (QR payload) and recovery codes, shown only once
+#[tauri::command]
+pub async fn begin_totp_enrollment(
+    app: tauri::AppHandle,
+    session: tauri::State<'_, SessionState>,
+    mfa: tauri::State<'_, MfaState>,
+    user_id: Option<String>,
+) -> AppResult<TotpEnrollment> {
+    let user = enrolling_user(&session, &mfa, user_id.as_deref())?;
+    let mut store = load_mfa_store(&app)?;
+
+    if store.confirmed(&user.user_id).is_some() {
+        return Err(AuthError::new(
+            AuthErrorKind::PermissionDenied,
+            "Authenticator is already enrolled; disable it before enrolling again",
+        )
+        .into());
+    }
+
+    let (record, recovery_codes) = MfaRecord::new();
+    let enrollment = TotpEnrollment::new(&user, &record, recovery_codes);
+    store.users.insert(user.user_id, record);
+    save_mfa_store(&app, &store)?;
+
+    Ok(enrollment)
+}
+
+/// Confirm TOTP enrollment with a code from the authenticator app
+/// During sign-in the confirmed code also counts as the MFA check for `start_session`
+#[tauri::command]
+pub async fn confirm_totp_enrollment(
+    app: tauri::AppHandle,
+    session: tauri::State<'_, SessionState>,
+    mfa: tauri::State<'_, MfaState>,
+    user_id: Option<String>,
+    code: String,
+) -> AppResult<()> {
+    let user = enrolling_user(&session, &mfa, user_id.as_deref())?;
+    let mut store = load_mfa_store(&app)?;
+
+    let record = store.users.get_mut(&user.user_id).ok_or_else(|| {
+        AuthError::new(
+            AuthErrorKind::InvalidCredentials,
+            "No authenticator enrollment is in progress",
+        )
+    })?;
+
+    if !record.verify_totp(&code, Utc::now()) {
+        return Err(AuthError::new(
+            AuthErrorKind::InvalidCredentials,
+            "Invalid authenticator code",
+        )
+        .into());
+    }
+
+    record.confirmed = true;
+    save_mfa_store(&app, &store)?;
+    if user_id.is_some() {
+        mfa.grant(&user.user_id);
+    }
     Ok(())
 }
 
+/// Disable TOTP for the signed-in user
+/// Not allowed when policy requires MFA for the user's role
+#[tauri::command]
+pub async fn disable_totp(
+    app: tauri::AppHandle,
+    session: tauri::State<'_, SessionState>,
+    code: String,
+) -> AppResult<()> {
+    let user = session.require_user()?;
+    if mfa_required(&load_config(&app).auth, &user.role_type) {
+        return Err(AuthError::new(
+            AuthErrorKind::PermissionDenied,
+            "Multi-factor authentication is required for this role",
+        )
+        .into());
+    }
+
+    let mut store = load_mfa_store(&app)?;
+    let verified = store
+        .users
+        .get_mut(&user.user_id)
+        .is_some_and(|record| record.verify(&code, Utc::now()));
+    if !verified {
+        return Err(AuthError::new(
+            AuthErrorKind::InvalidCredentials,
+            "Invalid authenticator or recovery code",
+        )
+        .into());
+    }
+
+    store.users.remove(&user.user_id);
+    save_mfa_store(&app, &store)?;
+    Ok(())
+}
+
+/// Get MFA policy and enrollment state for the signed-in user
+#[tauri::command]
+pub async fn get_mfa_status(
+    app: tauri::AppHandle,
+    session: tauri::State<'_, SessionState>,
+) -> AppResult<MfaStatus> {
+    let user = session.require_user()?;
+    let store = load_mfa_store(&app)?;
+    let record = store.confirmed(&user.user_id);
+
+    Ok(MfaStatus {
+        required: mfa_required(&load_config(&app).auth, &user.role_type),
+        enrolled: record.is_some(),
+        recovery_codes_remaining: record.map_or(0, |r| r.recovery_codes.len()),
+    })
+}
+
 // Note: Actual login/logout are handled via window commands
 // since they involve window switching. Additional auth logic
 // can be added here for token management, session persistence, etc.
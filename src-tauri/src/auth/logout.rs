@@ -0,0 +1,79 @@
+//! Logout
+//! Ends the current session: revokes its refresh token and clears per-user state
+
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+
+use super::mfa::MfaState;
+use super::session::{remove_stored_session, ActiveSession, SessionState};
+use super::types::RevokeTokenRequest;
+use crate::api::ApiClient;
+use crate::audit::{record_audit_event, AuditEvent, AuditEventKind};
+use crate::common::AppResult;
+use crate::idle::IdleMonitor;
+use crate::shortcuts::refresh_shortcuts;
+use crate::tray::refresh_tray;
+
+/// Event emitted once the session has been torn down and the login window is shown
+pub const LOGOUT_EVENT: &str = "session://logout";
+
+/// Result of ending a session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogoutOutcome {
+    pub user_id: Option<String>,
+    /// Whether the school API confirmed the refresh token is revoked
+    pub token_revoked: bool,
+}
+
+/// Ask the school API to revoke the session's refresh token
+async fn revoke_refresh_token(app: &tauri::AppHandle, session: &ActiveSession) -> AppResult<bool> {
+    let Some(refresh_token) = session.refresh_token.clone() else {
+        return Ok(false);
+    };
+    let request = RevokeTokenRequest {
+        refresh_token,
+        device_id: Some(session.device_id.clone()),
+    };
+
+    ApiClient::from_app(app)?
+        .with_token(session.access_token.clone())
+        .post_empty("auth/logout", &request)
+        .await?;
+    Ok(true)
+}
+
+/// End the current session
+///
+/// The session is dropped from memory and disk before the API call, so an
+/// unreachable server never keeps tokens on the device; a failed revocation
+/// is noted in the audit trail instead.
+pub async fn end_session(app: &tauri::AppHandle, kind: AuditEventKind) -> LogoutOutcome {
+    let session = app.state::<SessionState>().clear();
+    let _ = remove_stored_session(app);
+    app.state::<MfaState>().clear();
+    app.state::<IdleMonitor>().reset();
+    refresh_tray(app);
+    refresh_shortcuts(app);
+
+    let token_revoked = match &session {
+        Some(session) => match revoke_refresh_token(app, session).await {
+            Ok(revoked) => revoked,
+            Err(e) => {
+                log::warn!("Refresh token revocation failed: {}", e);
+                false
+            }
+        },
+        None => false,
+    };
+
+    let mut event = AuditEvent::new(kind).with_optional_user(session.as_ref().map(|s| &s.user));
+    if session.as_ref().is_some_and(|s| s.refresh_token.is_some()) && !token_revoked {
+        event = event.with_details("refresh token not revoked");
+    }
+    record_audit_event(app, event);
+
+    LogoutOutcome {
+        user_id: session.map(|s| s.user.user_id),
+        token_revoked,
+    }
+}
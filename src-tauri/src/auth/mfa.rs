@@ -0,0 +1,378 @@
+//! Multi-Factor Authentication
+//! TOTP enrollment, recovery codes and the per-login MFA pass
+
+use chrono::{DateTime, Utc};
+use data_encoding::BASE32_NOPAD;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use super::totp::{generate_secret, otpauth_uri, verify_code};
+use super::types::{AuthError, AuthErrorKind, AuthUser, LoginResponse, UserRoleType};
+use super::vault::{open, seal, session_key};
+use crate::common::{app_data_file, read_yaml, write_yaml, AppResult};
+use crate::config::AuthConfig;
+
+/// MFA enrollment file name
+const MFA_FILE_NAME: &str = "mfa.yaml";
+
+/// Issuer shown in authenticator apps
+const TOTP_ISSUER: &str = "Smart School";
+
+/// Recovery codes issued per enrollment
+const RECOVERY_CODE_COUNT: usize = 10;
+
+/// Accepted clock drift in time steps
+const TOTP_SKEW: u64 = 1;
+
+/// How long a verified MFA code allows starting a session
+const MFA_PASS_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// TOTP enrollment for one user on this device
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MfaRecord {
+    /// Base32 TOTP secret; kept on the device so codes can be checked offline
+    ///
+    /// Empty if it could not be decrypted, in which case only recovery codes work.
+    #[serde(skip)]
+    pub secret: String,
+    /// `secret` sealed with the vault key, as saved in `mfa.yaml`
+    #[serde(default)]
+    sealed_secret: String,
+    /// Plaintext secret from files written before seeds were sealed
+    #[serde(default, rename = "secret", skip_serializing)]
+    legacy_secret: Option<String>,
+    /// Set once the user proves their authenticator works
+    pub confirmed: bool,
+    /// SHA-256 hashes of unused recovery codes
+    pub recovery_codes: Vec<String>,
+    pub last_used_step: Option<u64>,
+    pub enrolled_at: DateTime<Utc>,
+}
+
+impl MfaRecord {
+    /// Create an unconfirmed enrollment, returning the plain recovery codes
+    pub fn new() -> (Self, Vec<String>) {
+        let codes: Vec<String> = (0..RECOVERY_CODE_COUNT)
+            .map(|_| generate_recovery_code())
+            .collect();
+
+        let record = Self {
+            secret: generate_secret(),
+            sealed_secret: String::new(),
+            legacy_secret: None,
+            confirmed: false,
+            recovery_codes: codes.iter().map(|code| hash_recovery_code(code)).collect(),
+            last_used_step: None,
+            enrolled_at: Utc::now(),
+        };
+
+        (record, codes)
+    }
+
+    /// Check a TOTP code, rejecting replays of an already used step
+    pub fn verify_totp(&mut self, code: &str, now: DateTime<Utc>) -> bool {
+        if self.secret.is_empty() {
+            return false;
+        }
+        let unix_secs = now.timestamp().max(0) as u64;
+        match verify_code(
+            &self.secret,
+            code,
+            unix_secs,
+            TOTP_SKEW,
+            self.last_used_step,
+        ) {
+            Some(step) => {
+                self.last_used_step = Some(step);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Check a TOTP code or consume a recovery code
+    pub fn verify(&mut self, code: &str, now: DateTime<Utc>) -> bool {
+        if self.verify_totp(code, now) {
+            return true;
+        }
+
+        let hash = hash_recovery_code(code);
+        match self.recovery_codes.iter().position(|h| *h == hash) {
+            Some(index) => {
+                self.recovery_codes.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// Enrollment data shown once to the user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TotpEnrollment {
+    pub secret: String,
+    /// `otpauth://` URI, also used as the QR code payload
+    pub otpauth_uri: String,
+    pub recovery_codes: Vec<String>,
+}
+
+impl TotpEnrollment {
+    pub fn new(user: &AuthUser, record: &MfaRecord, recovery_codes: Vec<String>) -> Self {
+        Self {
+            secret: record.secret.clone(),
+            otpauth_uri: otpauth_uri(TOTP_ISSUER, &user.username, &record.secret),
+            recovery_codes,
+        }
+    }
+}
+
+/// MFA state for the current user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MfaStatus {
+    /// Policy requires MFA for this user's role
+    pub required: bool,
+    pub enrolled: bool,
+    pub recovery_codes_remaining: usize,
+}
+
+/// Persisted enrollments by user id
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MfaStore {
+    pub users: BTreeMap<String, MfaRecord>,
+}
+
+impl MfaStore {
+    /// Confirmed enrollment for a user, if any
+    pub fn confirmed(&self, user_id: &str) -> Option<&MfaRecord> {
+        self.users.get(user_id).filter(|record| record.confirmed)
+    }
+}
+
+/// Sign-ins in progress: short-lived proof that a user passed MFA, and the
+/// school API's answer to their login; both are consumed when the session starts
+#[derive(Default)]
+pub struct MfaState {
+    passes: Mutex<HashMap<String, Instant>>,
+    /// Logins the school API just accepted, by user id
+    logins: Mutex<HashMap<String, (LoginResponse, Instant)>>,
+}
+
+impl MfaState {
+    /// Remember a login the school API accepted, so the session can start from it
+    pub fn note_login(&self, response: &LoginResponse) {
+        self.logins.lock().unwrap().insert(
+            response.user.user_id.clone(),
+            (response.clone(), Instant::now()),
+        );
+    }
+
+    /// Accepted login of a user part-way through signing in, if recent enough
+    pub fn pending_sign_in(&self, user_id: &str) -> Option<LoginResponse> {
+        self.logins
+            .lock()
+            .unwrap()
+            .get(user_id)
+            .filter(|(_, at)| at.elapsed() < MFA_PASS_TTL)
+            .map(|(login, _)| login.clone())
+    }
+
+    /// User part-way through signing in, if their login is recent enough
+    pub fn pending_login(&self, user_id: &str) -> Option<AuthUser> {
+        self.pending_sign_in(user_id).map(|login| login.user)
+    }
+
+    /// Forget a login once its session has started
+    pub fn finish_login(&self, user_id: &str) {
+        self.logins.lock().unwrap().remove(user_id);
+    }
+
+    pub fn grant(&self, user_id: &str) {
+        self.passes
+            .lock()
+            .unwrap()
+            .insert(user_id.to_string(), Instant::now());
+    }
+
+    /// Consume a pass, returning whether a fresh one existed
+    pub fn take(&self, user_id: &str) -> bool {
+        self.passes
+            .lock()
+            .unwrap()
+            .remove(user_id)
+            .is_some_and(|granted| granted.elapsed() < MFA_PASS_TTL)
+    }
+
+    /// Drop all passes and pending logins, e.g. on logout
+    pub fn clear(&self) {
+        self.passes.lock().unwrap().clear();
+        self.logins.lock().unwrap().clear();
+    }
+}
+
+/// Whether policy requires MFA for a role
+pub fn mfa_required(config: &AuthConfig, role: &UserRoleType) -> bool {
+    config.mfa_required_for_admins && role.is_admin()
+}
+
+/// Check whether a session may start, given the MFA policy, enrollment and a consumed pass
+pub fn check_session_mfa(required: bool, enrolled: bool, passed: bool) -> Result<(), AuthError> {
+    if passed || !(required || enrolled) {
+        return Ok(());
+    }
+    if enrolled {
+        Err(AuthError::new(
+            AuthErrorKind::MfaRequired,
+            "An authenticator code is required to sign in",
+        ))
+    } else {
+        Err(AuthError::new(
+            AuthErrorKind::MfaEnrollmentRequired,
+            "Multi-factor authentication is required for this role; set up an authenticator to sign in",
+        ))
+    }
+}
+
+fn generate_recovery_code() -> String {
+    let mut bytes = [0u8; 5];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    let code = BASE32_NOPAD.encode(&bytes).to_lowercase();
+    format!("{}-{}", &code[..4], &code[4..])
+}
+
+fn hash_recovery_code(code: &str) -> String {
+    let normalized = code.trim().to_lowercase();
+    hex::encode(Sha256::digest(normalized.as_bytes()))
+}
+
+/// Load MFA enrollments from disk, decrypting the TOTP secrets
+///
+/// Plaintext secrets from older files are sealed and saved back.
+pub fn load_mfa_store(app: &tauri::AppHandle) -> AppResult<MfaStore> {
+    let path = app_data_file(app, MFA_FILE_NAME)?;
+    let mut store: MfaStore = read_yaml(&path)?.unwrap_or_default();
+    if store.users.is_empty() {
+        return Ok(store);
+    }
+
+    let key = session_key(app)?;
+    let mut migrated = false;
+    for (user_id, record) in store.users.iter_mut() {
+        if let Some(secret) = record.legacy_secret.take() {
+            record.secret = secret;
+            migrated = true;
+            continue;
+        }
+        match open(&key, &record.sealed_secret).and_then(|bytes| String::from_utf8(bytes).ok()) {
+            Some(secret) => record.secret = secret,
+            None => log::warn!("TOTP secret for user {} could not be decrypted", user_id),
+        }
+    }
+
+    if migrated {
+        save_mfa_store(app, &store)?;
+    }
+    Ok(store)
+}
+
+/// Save MFA enrollments to disk with the TOTP secrets sealed by the vault key
+pub fn save_mfa_store(app: &tauri::AppHandle, store: &MfaStore) -> AppResult<()> {
+    let key = session_key(app)?;
+    let mut sealed = store.clone();
+    for record in sealed.users.values_mut() {
+        record.sealed_secret = seal(&key, record.secret.as_bytes());
+    }
+
+    let path = app_data_file(app, MFA_FILE_NAME)?;
+    write_yaml(&path, &sealed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::totp::{hotp, time_step};
+
+    #[test]
+    fn test_recovery_codes_are_single_use() {
+        let (mut record, codes) = MfaRecord::new();
+        assert_eq!(codes.len(), RECOVERY_CODE_COUNT);
+
+        let now = Utc::now();
+        assert!(record.verify(&codes[0].to_uppercase(), now));
+        assert!(!record.verify(&codes[0], now));
+        assert_eq!(record.recovery_codes.len(), RECOVERY_CODE_COUNT - 1);
+    }
+
+    #[test]
+    fn test_unenrolled_admin_needs_mfa() {
+        let config = AuthConfig::default();
+        let required = mfa_required(&config, &UserRoleType::Admin);
+        assert!(required);
+        assert!(!mfa_required(&config, &UserRoleType::Teacher));
+
+        let err = check_session_mfa(required, false, false).unwrap_err();
+        assert!(matches!(err.kind, AuthErrorKind::MfaEnrollmentRequired));
+        assert!(check_session_mfa(required, false, true).is_ok());
+
+        let err = check_session_mfa(false, true, false).unwrap_err();
+        assert!(matches!(err.kind, AuthErrorKind::MfaRequired));
+        assert!(check_session_mfa(false, false, false).is_ok());
+    }
+
+    #[test]
+    fn test_pending_login_is_single_use() {
+        let state = MfaState::default();
+        assert!(state.pending_sign_in("teacher_001").is_none());
+
+        let login = LoginResponse {
+            user: serde_json::from_str(include_str!("fixtures/teacher_user.json")).unwrap(),
+            access_token: "access".into(),
+            refresh_token: None,
+            expires_in: 3600,
+        };
+        let user_id = login.user.user_id.clone();
+        state.note_login(&login);
+        assert_eq!(state.pending_login(&user_id).unwrap().user_id, user_id);
+
+        state.finish_login(&user_id);
+        assert!(state.pending_sign_in(&user_id).is_none());
+    }
+
+    #[test]
+    fn test_totp_code_cannot_be_replayed() {
+        let (mut record, _) = MfaRecord::new();
+        let now = Utc::now();
+        let secret = BASE32_NOPAD.decode(record.secret.as_bytes()).unwrap();
+        let code = format!("{:06}", hotp(&secret, time_step(now.timestamp() as u64)));
+
+        assert!(record.verify(&code, now));
+        assert!(!record.verify(&code, now));
+    }
+
+    #[test]
+    fn test_secret_is_not_written_in_plaintext() {
+        let (mut record, _) = MfaRecord::new();
+        record.sealed_secret = seal(&[7u8; 32], record.secret.as_bytes());
+        let yaml = serde_yaml::to_string(&record).unwrap();
+        assert!(!yaml.contains(&record.secret));
+
+        // Older files still load their plaintext secret for migration
+        let legacy = yaml.replace("sealed_secret:", "secret:");
+        let legacy: MfaRecord = serde_yaml::from_str(&legacy).unwrap();
+        assert!(legacy.secret.is_empty());
+        assert!(legacy.legacy_secret.is_some());
+    }
+
+    #[test]
+    fn test_missing_secret_rejects_every_code() {
+        let (mut record, _) = MfaRecord::new();
+        record.secret.clear();
+        // The code an empty HMAC key would produce must not pass
+        let now = Utc::now();
+        let code = format!("{:06}", hotp(&[], time_step(now.timestamp() as u64)));
+        assert!(!record.verify_totp(&code, now));
+    }
+}
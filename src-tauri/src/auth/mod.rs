@@ -4,8 +4,28 @@
 #![allow(dead_code)]
 #![allow(unused_imports)]
 
+mod accounts;
 mod commands;
+mod logout;
+mod mfa;
+mod password;
+mod pin;
+mod secret;
+mod session;
+mod throttle;
+mod totp;
 mod types;
+mod vault;
 
+pub use accounts::*;
 pub use commands::*;
+pub use logout::*;
+pub use mfa::*;
+pub use password::*;
+pub use pin::*;
+pub use secret::*;
+pub use session::*;
+pub use throttle::*;
+pub use totp::*;
 pub use types::*;
+pub use vault::*;
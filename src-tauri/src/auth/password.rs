@@ -0,0 +1,179 @@
+//! Password Policy
+//! Local password strength checks run before a new password is sent to the school API
+
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use crate::config::PasswordPolicyConfig;
+
+/// A single rule a password failed
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "rule", rename_all = "snake_case")]
+pub enum PasswordViolation {
+    TooShort {
+        min: usize,
+    },
+    TooLong {
+        max: usize,
+    },
+    /// Fewer distinct character classes than required
+    TooFewClasses {
+        required: usize,
+        found: usize,
+    },
+    ContainsUsername,
+    /// Found in the breached password list
+    Breached,
+}
+
+/// Result of checking a password against the policy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PasswordCheck {
+    pub valid: bool,
+    pub violations: Vec<PasswordViolation>,
+}
+
+/// Check a password against the policy
+///
+/// `breached_list` is only read when the other rules pass, so obviously weak
+/// passwords don't pay for a file scan.
+pub fn check_password(
+    policy: &PasswordPolicyConfig,
+    password: &str,
+    username: Option<&str>,
+    breached_list: Option<&Path>,
+) -> PasswordCheck {
+    let mut violations = Vec::new();
+    let length = password.chars().count();
+
+    if length < policy.min_length {
+        violations.push(PasswordViolation::TooShort {
+            min: policy.min_length,
+        });
+    }
+    if length > policy.max_length {
+        violations.push(PasswordViolation::TooLong {
+            max: policy.max_length,
+        });
+    }
+
+    let found = character_classes(password);
+    if found < policy.min_character_classes {
+        violations.push(PasswordViolation::TooFewClasses {
+            required: policy.min_character_classes,
+            found,
+        });
+    }
+
+    let username = username.map(|u| u.trim().to_lowercase());
+    if let Some(username) = username.filter(|u| u.chars().count() >= 3) {
+        if password.to_lowercase().contains(&username) {
+            violations.push(PasswordViolation::ContainsUsername);
+        }
+    }
+
+    if violations.is_empty() && breached_list.is_some_and(|path| is_breached(path, password)) {
+        violations.push(PasswordViolation::Breached);
+    }
+
+    PasswordCheck {
+        valid: violations.is_empty(),
+        violations,
+    }
+}
+
+/// Number of distinct classes among lowercase, uppercase, digits and symbols
+fn character_classes(password: &str) -> usize {
+    let checks: [fn(&char) -> bool; 4] = [
+        char::is_ascii_lowercase,
+        char::is_ascii_uppercase,
+        char::is_ascii_digit,
+        |c| !c.is_ascii_alphanumeric(),
+    ];
+    checks
+        .iter()
+        .filter(|check| password.chars().any(|c| check(&c)))
+        .count()
+}
+
+/// Look a password up in a breached list
+///
+/// Lines are either the plain password or its SHA-1 hex digest, optionally
+/// followed by `:count` as in the Have I Been Pwned downloads. A missing or
+/// unreadable file counts as not breached.
+fn is_breached(path: &Path, password: &str) -> bool {
+    let Ok(file) = File::open(path) else {
+        return false;
+    };
+    let sha1 = hex::encode_upper(Sha1::digest(password.as_bytes()));
+
+    BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .any(|line| {
+            let line = line.trim_end_matches(['\r', '\n']);
+            if line == password {
+                return true;
+            }
+            let hash = line.split(':').next().unwrap_or_default().trim();
+            hash.len() == 40 && hash.eq_ignore_ascii_case(&sha1)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn policy() -> PasswordPolicyConfig {
+        PasswordPolicyConfig::default()
+    }
+
+    #[test]
+    fn test_itemized_violations() {
+        let check = check_password(&policy(), "teacher1", Some("Teacher"), None);
+        assert!(!check.valid);
+        assert_eq!(
+            check.violations,
+            vec![
+                PasswordViolation::TooFewClasses {
+                    required: 3,
+                    found: 2
+                },
+                PasswordViolation::ContainsUsername,
+            ]
+        );
+
+        let check = check_password(&policy(), "aB1!", None, None);
+        assert_eq!(
+            check.violations,
+            vec![PasswordViolation::TooShort { min: 8 }]
+        );
+
+        assert!(check_password(&policy(), "Correct-Horse-9", Some("teacher01"), None).valid);
+    }
+
+    #[test]
+    fn test_breached_list_plain_and_sha1() {
+        let path =
+            std::env::temp_dir().join(format!("smart-school-breached-{}.txt", std::process::id()));
+        let mut file = File::create(&path).unwrap();
+        writeln!(file, "Password123!").unwrap();
+        let sha1 = hex::encode_upper(Sha1::digest(b"Summer-2024x"));
+        writeln!(file, "{}:42", sha1).unwrap();
+        drop(file);
+
+        let breached = |password| check_password(&policy(), password, None, Some(&path));
+        assert_eq!(
+            breached("Password123!").violations,
+            vec![PasswordViolation::Breached]
+        );
+        assert!(!breached("Summer-2024x").valid);
+        assert!(breached("Winter-2024x").valid);
+
+        std::fs::remove_file(path).unwrap();
+    }
+}
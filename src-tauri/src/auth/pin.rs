@@ -0,0 +1,121 @@
+//! Local PIN
+//! Short device-local PIN that can only resume an existing session
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::secret::SecretHash;
+use super::types::{AuthError, AuthErrorKind};
+
+/// Failed PIN entries before the PIN is invalidated
+pub const MAX_PIN_FAILURES: u32 = 5;
+
+/// Allowed PIN length (digits)
+const PIN_LENGTH: std::ops::RangeInclusive<usize> = 4..=8;
+
+/// Hashed PIN bound to one session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PinRecord {
+    /// Hash of `session_id:pin`, so the PIN is useless for any other session
+    pub hash: SecretHash,
+    /// Kept in the OS keychain rather than session.yaml, see `save_stored_session`
+    #[serde(skip)]
+    pub failures: u32,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Outcome of a PIN check
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PinCheck {
+    Valid,
+    Invalid {
+        remaining: u32,
+    },
+    /// Too many failures; the PIN must be discarded
+    Exhausted,
+}
+
+impl PinRecord {
+    pub fn new(session_id: &str, pin: &str) -> Result<Self, AuthError> {
+        validate_pin(pin)?;
+        Ok(Self {
+            hash: SecretHash::new(&bind(session_id, pin)),
+            failures: 0,
+            created_at: Utc::now(),
+        })
+    }
+
+    /// Verify a PIN, counting failures
+    pub fn check(&mut self, session_id: &str, pin: &str) -> PinCheck {
+        if self.failures >= MAX_PIN_FAILURES {
+            return PinCheck::Exhausted;
+        }
+
+        if self.hash.verify(&bind(session_id, pin)) {
+            self.failures = 0;
+            return PinCheck::Valid;
+        }
+
+        self.failures += 1;
+        match MAX_PIN_FAILURES - self.failures {
+            0 => PinCheck::Exhausted,
+            remaining => PinCheck::Invalid { remaining },
+        }
+    }
+}
+
+/// Check PIN format: digits only, 4 to 8 long
+pub fn validate_pin(pin: &str) -> Result<(), AuthError> {
+    if PIN_LENGTH.contains(&pin.len()) && pin.chars().all(|c| c.is_ascii_digit()) {
+        Ok(())
+    } else {
+        Err(AuthError::new(
+            AuthErrorKind::InvalidCredentials,
+            format!(
+                "PIN must be {} to {} digits",
+                PIN_LENGTH.start(),
+                PIN_LENGTH.end()
+            ),
+        ))
+    }
+}
+
+fn bind(session_id: &str, pin: &str) -> String {
+    format!("{}:{}", session_id, pin)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pin_is_bound_to_session() {
+        let mut record = PinRecord::new("session-a", "1234").unwrap();
+        assert_eq!(record.check("session-a", "1234"), PinCheck::Valid);
+        assert_eq!(
+            record.check("session-b", "1234"),
+            PinCheck::Invalid { remaining: 4 }
+        );
+    }
+
+    #[test]
+    fn test_pin_exhausted_after_max_failures() {
+        let mut record = PinRecord::new("s", "2468").unwrap();
+        for _ in 1..MAX_PIN_FAILURES {
+            assert!(matches!(
+                record.check("s", "0000"),
+                PinCheck::Invalid { .. }
+            ));
+        }
+        assert_eq!(record.check("s", "0000"), PinCheck::Exhausted);
+        assert_eq!(record.check("s", "2468"), PinCheck::Exhausted);
+    }
+
+    #[test]
+    fn test_validate_pin() {
+        assert!(validate_pin("1234").is_ok());
+        assert!(validate_pin("123").is_err());
+        assert!(validate_pin("12a4").is_err());
+        assert!(validate_pin("123456789").is_err());
+    }
+}
@@ -0,0 +1,59 @@
+//! Secret Hashing
+//! Salted PBKDF2 hashes for secrets verified locally (passwords, card ids)
+
+use pbkdf2::pbkdf2_hmac;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+/// PBKDF2 iteration count
+const PBKDF2_ROUNDS: u32 = 50_000;
+
+/// Salt length in bytes
+const SALT_LEN: usize = 16;
+
+/// Derived hash length in bytes
+const HASH_LEN: usize = 32;
+
+/// Salted hash of a locally verified secret
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SecretHash {
+    /// Hex-encoded random salt
+    pub salt: String,
+    /// Hex-encoded PBKDF2-HMAC-SHA256 output
+    pub hash: String,
+}
+
+impl SecretHash {
+    /// Hash a secret with a fresh random salt
+    pub fn new(secret: &str) -> Self {
+        let mut salt = [0u8; SALT_LEN];
+        rand::thread_rng().fill_bytes(&mut salt);
+
+        Self {
+            salt: hex::encode(salt),
+            hash: hex::encode(derive(secret, &salt)),
+        }
+    }
+
+    /// Check a secret against this hash in constant time
+    pub fn verify(&self, secret: &str) -> bool {
+        let (Ok(salt), Ok(expected)) = (hex::decode(&self.salt), hex::decode(&self.hash)) else {
+            return false;
+        };
+
+        let actual = derive(secret, &salt);
+        actual.len() == expected.len()
+            && actual
+                .iter()
+                .zip(expected.iter())
+                .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+                == 0
+    }
+}
+
+fn derive(secret: &str, salt: &[u8]) -> [u8; HASH_LEN] {
+    let mut out = [0u8; HASH_LEN];
+    pbkdf2_hmac::<Sha256>(secret.as_bytes(), salt, PBKDF2_ROUNDS, &mut out);
+    out
+}
@@ -0,0 +1,442 @@
+//! Session State
+//! State for the user signed in on this device, persisted so it can be resumed with a PIN
+
+use chrono::{DateTime, Duration, Utc};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::sync::Mutex;
+
+use super::pin::{PinCheck, PinRecord};
+use super::secret::SecretHash;
+use super::types::{
+    AuthError, AuthErrorKind, AuthUser, LoginResponse, StartSessionRequest, UnlockMethod,
+};
+use super::vault::{load_session_key, open, seal, session_key};
+use crate::common::{
+    app_data_file, keychain_delete, keychain_get, keychain_set, read_yaml, write_yaml, AppResult,
+};
+
+/// Stored session file name
+const SESSION_FILE_NAME: &str = "session.yaml";
+
+/// Keychain entry holding `session_id:failures` for the stored PIN
+const PIN_FAILURES_KEY: &str = "pin-failures";
+
+/// The signed-in user together with the secrets accepted to unlock the session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActiveSession {
+    /// Random id generated per sign-in; a PIN is only valid for this id
+    pub session_id: String,
+    pub user: AuthUser,
+    pub started_at: DateTime<Utc>,
+    /// When the refresh token expires; the session cannot be resumed afterwards
+    pub refresh_expires_at: Option<DateTime<Utc>>,
+    /// Hash of the password used to sign in (memory only)
+    #[serde(skip)]
+    pub password: Option<SecretHash>,
+    /// Hash of the card id used to sign in (memory only)
+    #[serde(skip)]
+    pub card: Option<SecretHash>,
+    /// Device-local PIN for resuming this session
+    pub pin: Option<PinRecord>,
+    /// Device the session was started on; it cannot be resumed on another install
+    #[serde(default)]
+    pub device_id: String,
+    #[serde(default)]
+    pub access_token: Option<String>,
+    #[serde(default)]
+    pub refresh_token: Option<String>,
+}
+
+impl ActiveSession {
+    /// Session for the user and tokens of a verified `login`
+    pub fn new(login: LoginResponse, request: StartSessionRequest, device_id: String) -> Self {
+        let mut id = [0u8; 16];
+        rand::thread_rng().fill_bytes(&mut id);
+        let now = Utc::now();
+
+        Self {
+            session_id: hex::encode(id),
+            user: login.user,
+            started_at: now,
+            refresh_expires_at: request
+                .refresh_expires_in
+                .map(|secs| now + Duration::seconds(secs.min(i64::MAX as u64) as i64)),
+            password: request.password.as_deref().map(SecretHash::new),
+            card: request.card_uid.as_deref().map(SecretHash::new),
+            pin: None,
+            device_id,
+            access_token: Some(login.access_token),
+            refresh_token: login.refresh_token,
+        }
+    }
+
+    pub fn is_refresh_expired(&self, now: DateTime<Utc>) -> bool {
+        self.refresh_expires_at
+            .is_some_and(|expires| now >= expires)
+    }
+}
+
+/// Managed state holding the current session, if any
+#[derive(Default)]
+pub struct SessionState {
+    session: Mutex<Option<ActiveSession>>,
+}
+
+impl SessionState {
+    /// Replace the current session
+    pub fn start(&self, session: ActiveSession) {
+        *self.session.lock().unwrap() = Some(session);
+    }
+
+    /// Drop the current session, returning it
+    pub fn clear(&self) -> Option<ActiveSession> {
+        self.session.lock().unwrap().take()
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.session.lock().unwrap().is_some()
+    }
+
+    pub fn current_user(&self) -> Option<AuthUser> {
+        self.session
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|session| session.user.clone())
+    }
+
+    /// Access token for calling the school API as the current user
+    pub fn access_token(&self) -> Option<String> {
+        self.session
+            .lock()
+            .unwrap()
+            .as_ref()
+            .and_then(|session| session.access_token.clone())
+    }
+
+    /// Refresh token and bound device of the current session
+    pub fn refresh_credentials(&self) -> Result<(String, String), AuthError> {
+        let guard = self.session.lock().unwrap();
+        let session = guard.as_ref().ok_or_else(no_session)?;
+        let token = session.refresh_token.clone().ok_or_else(|| {
+            AuthError::new(
+                AuthErrorKind::TokenInvalid,
+                "Session has no refresh token, please sign in again",
+            )
+        })?;
+        Ok((token, session.device_id.clone()))
+    }
+
+    /// Store tokens returned by a refresh
+    pub fn update_tokens(&self, access_token: String, refresh_token: Option<String>) {
+        if let Some(session) = self.session.lock().unwrap().as_mut() {
+            session.access_token = Some(access_token);
+            if refresh_token.is_some() {
+                session.refresh_token = refresh_token;
+            }
+        }
+    }
+
+    /// Copy of the current session, e.g. for persisting it
+    pub fn snapshot(&self) -> Option<ActiveSession> {
+        self.session.lock().unwrap().clone()
+    }
+
+    /// Return the current user, failing if nobody is signed in
+    pub fn require_user(&self) -> Result<AuthUser, AuthError> {
+        self.current_user().ok_or_else(no_session)
+    }
+
+    /// Return the current user if they hold an admin role
+    pub fn require_admin(&self) -> Result<AuthUser, AuthError> {
+        let user = self.require_user()?;
+        if user.role_type.is_admin() {
+            Ok(user)
+        } else {
+            Err(AuthError::new(
+                AuthErrorKind::PermissionDenied,
+                "This action requires an administrator",
+            ))
+        }
+    }
+
+    /// Set a PIN after re-checking the password used to sign in
+    pub fn set_pin(&self, password: &str, pin: &str) -> Result<(), AuthError> {
+        let mut guard = self.session.lock().unwrap();
+        let session = guard.as_mut().ok_or_else(no_session)?;
+        confirm_password(session, password, "set")?;
+
+        session.pin = Some(PinRecord::new(&session.session_id, pin)?);
+        Ok(())
+    }
+
+    /// Replace the password hash after a password change so unlocking keeps working
+    pub fn replace_password(&self, password: &str) {
+        if let Some(session) = self.session.lock().unwrap().as_mut() {
+            session.password = Some(SecretHash::new(password));
+        }
+    }
+
+    /// Remove the PIN after re-checking the password used to sign in,
+    /// returning whether one was set
+    ///
+    /// A session resumed with its PIN has no password hash, so there the
+    /// PIN can only be dropped by signing out.
+    pub fn remove_pin(&self, password: &str) -> Result<bool, AuthError> {
+        let mut guard = self.session.lock().unwrap();
+        let session = guard.as_mut().ok_or_else(no_session)?;
+        confirm_password(session, password, "removed")?;
+
+        Ok(session.pin.take().is_some())
+    }
+
+    /// Check an unlock credential against the current session
+    pub fn verify_unlock(&self, method: UnlockMethod, secret: &str) -> Result<(), AuthError> {
+        let mut guard = self.session.lock().unwrap();
+        let session = guard.as_mut().ok_or_else(no_session)?;
+
+        let verified = match method {
+            UnlockMethod::Password => session
+                .password
+                .as_ref()
+                .is_some_and(|hash| hash.verify(secret)),
+            UnlockMethod::Card => session
+                .card
+                .as_ref()
+                .is_some_and(|hash| hash.verify(secret)),
+            UnlockMethod::Pin => return check_pin(session, secret),
+        };
+
+        if verified {
+            Ok(())
+        } else {
+            Err(AuthError::new(
+                AuthErrorKind::InvalidCredentials,
+                "Unlock credential does not match the signed-in user",
+            ))
+        }
+    }
+}
+
+fn confirm_password(
+    session: &ActiveSession,
+    password: &str,
+    action: &str,
+) -> Result<(), AuthError> {
+    let password_ok = session
+        .password
+        .as_ref()
+        .is_some_and(|hash| hash.verify(password));
+    if password_ok {
+        Ok(())
+    } else {
+        Err(AuthError::new(
+            AuthErrorKind::InvalidCredentials,
+            format!(
+                "A PIN can only be {} after confirming the password used to sign in",
+                action
+            ),
+        ))
+    }
+}
+
+fn check_pin(session: &mut ActiveSession, pin: &str) -> Result<(), AuthError> {
+    if session.is_refresh_expired(Utc::now()) {
+        session.pin = None;
+        return Err(AuthError::new(
+            AuthErrorKind::TokenExpired,
+            "Session has expired, please sign in with your password",
+        ));
+    }
+
+    let Some(record) = session.pin.as_mut() else {
+        return Err(AuthError::new(
+            AuthErrorKind::InvalidCredentials,
+            "No PIN is set for this session",
+        ));
+    };
+
+    match record.check(&session.session_id, pin) {
+        PinCheck::Valid => Ok(()),
+        PinCheck::Invalid { remaining } => Err(AuthError::new(
+            AuthErrorKind::InvalidCredentials,
+            format!("Incorrect PIN, {} attempts remaining", remaining),
+        )),
+        PinCheck::Exhausted => {
+            session.pin = None;
+            Err(AuthError::new(
+                AuthErrorKind::AccountLocked,
+                "Too many incorrect PIN entries, please sign in with your password",
+            ))
+        }
+    }
+}
+
+fn no_session() -> AuthError {
+    AuthError::new(AuthErrorKind::TokenInvalid, "No user is signed in")
+}
+
+/// On-disk form of the session: the whole `ActiveSession`, tokens included,
+/// encrypted with the session key from the OS keychain
+#[derive(Debug, Serialize, Deserialize)]
+struct SealedSession {
+    sealed: String,
+}
+
+/// Load the session stored by a previous run
+///
+/// Returns None if there is no stored session, or if it cannot be decrypted
+/// with this OS account's session key (other machine, old plaintext format).
+pub fn load_stored_session(app: &tauri::AppHandle) -> AppResult<Option<ActiveSession>> {
+    let path = app_data_file(app, SESSION_FILE_NAME)?;
+    let stored = match read_yaml::<SealedSession>(&path) {
+        Ok(Some(stored)) => stored,
+        Ok(None) => return Ok(None),
+        Err(e) => {
+            log::warn!("Discarding unreadable stored session: {}", e);
+            return Ok(None);
+        }
+    };
+    let Some(key) = load_session_key(app)? else {
+        return Ok(None);
+    };
+    let Some(mut session) = open(&key, &stored.sealed)
+        .and_then(|json| serde_json::from_slice::<ActiveSession>(&json).ok())
+    else {
+        return Ok(None);
+    };
+
+    // A missing counter means it was tampered with, so the PIN is dropped
+    if session.pin.is_some() {
+        match load_pin_failures(app, &session.session_id)? {
+            Some(failures) => session.pin.as_mut().unwrap().failures = failures,
+            None => session.pin = None,
+        }
+    }
+    Ok(Some(session))
+}
+
+/// Persist the current session, or remove the stored one if there is none
+///
+/// Without a usable keychain the session is kept in memory only, since the
+/// tokens must not be written to disk in plaintext.
+pub fn save_stored_session(app: &tauri::AppHandle, state: &SessionState) -> AppResult<()> {
+    let Some(session) = state.snapshot() else {
+        return remove_stored_session(app);
+    };
+
+    let key = match session_key(app) {
+        Ok(key) => key,
+        Err(e) => {
+            log::warn!("Session will not be persisted: {}", e);
+            return remove_stored_session(app);
+        }
+    };
+    // The PIN failure counter lives in the keychain, where restoring an older
+    // session.yaml cannot reset it
+    match &session.pin {
+        Some(pin) => keychain_set(
+            app,
+            PIN_FAILURES_KEY,
+            &format!("{}:{}", session.session_id, pin.failures),
+        )?,
+        None => keychain_delete(app, PIN_FAILURES_KEY)?,
+    }
+
+    let json = serde_json::to_vec(&session)?;
+    let stored = SealedSession {
+        sealed: seal(&key, &json),
+    };
+    write_yaml(&app_data_file(app, SESSION_FILE_NAME)?, &stored)
+}
+
+/// PIN failure count stored for `session_id`, None if there is none
+fn load_pin_failures(app: &tauri::AppHandle, session_id: &str) -> AppResult<Option<u32>> {
+    Ok(keychain_get(app, PIN_FAILURES_KEY)?.and_then(|value| {
+        let (id, failures) = value.split_once(':')?;
+        (id == session_id).then(|| failures.parse().ok()).flatten()
+    }))
+}
+
+/// Delete the stored session file
+pub fn remove_stored_session(app: &tauri::AppHandle) -> AppResult<()> {
+    if let Err(e) = keychain_delete(app, PIN_FAILURES_KEY) {
+        log::warn!("Could not clear the PIN failure counter: {}", e);
+    }
+
+    let path = app_data_file(app, SESSION_FILE_NAME)?;
+    if path.exists() {
+        fs::remove_file(path)?;
+    }
+    Ok(())
+}
+
+/// Restore a stored session at startup
+///
+/// Only sessions with a PIN and an unexpired refresh token are restored, since
+/// the password hash is never persisted and a PIN is the only way to resume.
+/// The stored session is encrypted with a key held in the OS keychain, so a data
+/// dir copied to another machine or OS account cannot be decrypted and is
+/// discarded; the device id check additionally rejects sessions from other installs.
+/// Returns true if a session was restored; it should start out locked.
+pub fn restore_session(
+    app: &tauri::AppHandle,
+    state: &SessionState,
+    device_id: &str,
+) -> AppResult<bool> {
+    let Some(session) = load_stored_session(app)? else {
+        remove_stored_session(app)?;
+        return Ok(false);
+    };
+
+    if session.pin.is_none()
+        || session.device_id != device_id
+        || session.is_refresh_expired(Utc::now())
+    {
+        remove_stored_session(app)?;
+        return Ok(false);
+    }
+
+    state.start(session);
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signed_in(password: Option<&str>) -> SessionState {
+        let login = LoginResponse {
+            user: serde_json::from_str(include_str!("fixtures/teacher_user.json")).unwrap(),
+            access_token: "access".into(),
+            refresh_token: None,
+            expires_in: 3600,
+        };
+        let request = StartSessionRequest {
+            user_id: login.user.user_id.clone(),
+            password: password.map(str::to_string),
+            card_uid: None,
+            refresh_expires_in: None,
+        };
+        let state = SessionState::default();
+        state.start(ActiveSession::new(login, request, "device".into()));
+        state
+    }
+
+    #[test]
+    fn test_removing_pin_needs_password() {
+        let state = signed_in(Some("secret"));
+        state.set_pin("secret", "1234").unwrap();
+
+        assert!(state.remove_pin("wrong").is_err());
+        assert!(state.snapshot().unwrap().pin.is_some());
+        assert!(state.remove_pin("secret").unwrap());
+        assert!(!state.remove_pin("secret").unwrap());
+
+        // Resumed with a PIN: no password hash, so the PIN stays until sign-out
+        let resumed = signed_in(None);
+        assert!(resumed.remove_pin("secret").is_err());
+    }
+}
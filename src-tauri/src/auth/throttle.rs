@@ -0,0 +1,358 @@
+//! Login Throttling
+//! Failed-attempt counters with exponential backoff and lockout, persisted across restarts
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+use super::types::{AuthError, AuthErrorKind};
+use crate::common::{app_data_file, read_yaml, write_yaml, AppResult};
+
+/// Login attempts file name
+const LOGIN_ATTEMPTS_FILE_NAME: &str = "login_attempts.yaml";
+
+/// Backoff and lockout thresholds for one counter
+#[derive(Debug, Clone, Copy)]
+pub struct ThrottlePolicy {
+    /// Failures allowed before any delay applies
+    pub free_attempts: u32,
+    /// Delay after the first throttled failure, doubled for each further failure
+    pub base_delay_secs: i64,
+    pub max_delay_secs: i64,
+    /// Failures that trigger a lockout (`u32::MAX` disables lockout)
+    pub lockout_threshold: u32,
+    pub lockout_secs: i64,
+    /// Quiet period after which failures are forgotten
+    pub reset_after_secs: i64,
+}
+
+impl ThrottlePolicy {
+    /// Policy applied to each username
+    pub const PER_USER: Self = Self {
+        free_attempts: 3,
+        base_delay_secs: 2,
+        max_delay_secs: 300,
+        lockout_threshold: 10,
+        lockout_secs: 30 * 60,
+        reset_after_secs: 60 * 60,
+    };
+
+    /// Policy applied to all failures on this device, regardless of username
+    pub const GLOBAL: Self = Self {
+        free_attempts: 20,
+        base_delay_secs: 1,
+        max_delay_secs: 60,
+        lockout_threshold: u32::MAX,
+        lockout_secs: 0,
+        reset_after_secs: 5 * 60,
+    };
+}
+
+/// Failure counter for a username or for the whole device
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct AttemptRecord {
+    pub failures: u32,
+    pub last_failure: Option<DateTime<Utc>>,
+}
+
+impl AttemptRecord {
+    /// Failures that still count, ignoring those older than the reset period
+    fn active_failures(&self, policy: &ThrottlePolicy, now: DateTime<Utc>) -> u32 {
+        match self.last_failure {
+            Some(last) if now - last < Duration::seconds(policy.reset_after_secs) => self.failures,
+            _ => 0,
+        }
+    }
+
+    pub fn record_failure(&mut self, policy: &ThrottlePolicy, now: DateTime<Utc>) {
+        self.failures = self.active_failures(policy, now).saturating_add(1);
+        self.last_failure = Some(now);
+    }
+
+    /// Remaining wait before the next attempt and whether it is a lockout
+    pub fn retry_after(&self, policy: &ThrottlePolicy, now: DateTime<Utc>) -> Option<(u64, bool)> {
+        let failures = self.active_failures(policy, now);
+        let last = self.last_failure?;
+
+        let (wait_secs, locked) = if failures >= policy.lockout_threshold {
+            (policy.lockout_secs, true)
+        } else if failures > policy.free_attempts {
+            let exponent = (failures - policy.free_attempts - 1).min(30);
+            let delay = policy.base_delay_secs.saturating_mul(1 << exponent);
+            (delay.min(policy.max_delay_secs), false)
+        } else {
+            return None;
+        };
+
+        let remaining = (last + Duration::seconds(wait_secs) - now).num_seconds();
+        (remaining > 0).then_some((remaining as u64, locked))
+    }
+}
+
+/// Throttle status after recording an attempt
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoginThrottleStatus {
+    pub failures: u32,
+    pub retry_after_secs: Option<u64>,
+    pub locked: bool,
+}
+
+/// Persisted per-username and global attempt counters
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LoginThrottle {
+    pub users: BTreeMap<String, AttemptRecord>,
+    pub global: AttemptRecord,
+}
+
+impl LoginThrottle {
+    /// Fail with a lockout or backoff error if `username` may not attempt a login now
+    pub fn check(&self, username: &str, now: DateTime<Utc>) -> Result<(), AuthError> {
+        let user_wait = self
+            .users
+            .get(&normalize(username))
+            .and_then(|record| record.retry_after(&ThrottlePolicy::PER_USER, now));
+
+        if let Some((secs, true)) = user_wait {
+            return Err(AuthError::new(
+                AuthErrorKind::AccountLocked,
+                "Account is locked after too many failed login attempts",
+            )
+            .with_retry_after(secs));
+        }
+
+        let global_wait = self.global.retry_after(&ThrottlePolicy::GLOBAL, now);
+        let wait = [user_wait, global_wait]
+            .into_iter()
+            .flatten()
+            .map(|(secs, _)| secs)
+            .max();
+
+        match wait {
+            Some(secs) => Err(AuthError::new(
+                AuthErrorKind::TooManyAttempts,
+                "Too many failed login attempts, please wait before trying again",
+            )
+            .with_retry_after(secs)),
+            None => Ok(()),
+        }
+    }
+
+    /// Record a login result; success resets the username's counter
+    pub fn record(
+        &mut self,
+        username: &str,
+        success: bool,
+        now: DateTime<Utc>,
+    ) -> LoginThrottleStatus {
+        let key = normalize(username);
+
+        if success {
+            self.users.remove(&key);
+        } else {
+            self.users
+                .entry(key.clone())
+                .or_default()
+                .record_failure(&ThrottlePolicy::PER_USER, now);
+            self.global.record_failure(&ThrottlePolicy::GLOBAL, now);
+        }
+
+        self.prune(now);
+
+        let record = self.users.get(&key).cloned().unwrap_or_default();
+        let wait = record.retry_after(&ThrottlePolicy::PER_USER, now);
+        LoginThrottleStatus {
+            failures: record.failures,
+            retry_after_secs: wait.map(|(secs, _)| secs),
+            locked: wait.is_some_and(|(_, locked)| locked),
+        }
+    }
+
+    /// Check `username`, then count the attempt as a failure until its result is known
+    ///
+    /// Counting up front keeps concurrent attempts from all passing the same check.
+    pub fn begin(&mut self, username: &str, now: DateTime<Utc>) -> Result<(), AuthError> {
+        self.check(username, now)?;
+        self.record(username, false, now);
+        Ok(())
+    }
+
+    /// Take back the failure counted by `begin` for an attempt that was not rejected
+    pub fn cancel(&mut self, username: &str) {
+        let key = normalize(username);
+        if let Some(record) = self.users.get_mut(&key) {
+            record.failures = record.failures.saturating_sub(1);
+            if record.failures == 0 {
+                self.users.remove(&key);
+            }
+        }
+        self.global.failures = self.global.failures.saturating_sub(1);
+    }
+
+    /// Clear lockouts for one username, or for everyone when `None`
+    pub fn clear(&mut self, username: Option<&str>) {
+        match username {
+            Some(username) => {
+                self.users.remove(&normalize(username));
+            }
+            None => *self = Self::default(),
+        }
+    }
+
+    /// Drop counters that no longer affect anything
+    fn prune(&mut self, now: DateTime<Utc>) {
+        let policy = ThrottlePolicy::PER_USER;
+        self.users
+            .retain(|_, record| record.active_failures(&policy, now) > 0);
+    }
+}
+
+fn normalize(username: &str) -> String {
+    username.trim().to_lowercase()
+}
+
+/// Managed state serializing load/modify/save of the attempt counters
+#[derive(Default)]
+pub struct LoginThrottleState {
+    file: Mutex<()>,
+}
+
+impl LoginThrottleState {
+    /// Fail with a lockout or backoff error if `key` may not attempt a login now
+    pub fn check(&self, app: &tauri::AppHandle, key: &str) -> AppResult<()> {
+        let _file = self.file.lock().unwrap();
+        Ok(load_login_throttle(app)?.check(key, Utc::now())?)
+    }
+
+    /// Record an attempt for `key` and persist the counters
+    pub fn record(
+        &self,
+        app: &tauri::AppHandle,
+        key: &str,
+        success: bool,
+    ) -> AppResult<LoginThrottleStatus> {
+        let _file = self.file.lock().unwrap();
+        let mut throttle = load_login_throttle(app)?;
+        let status = throttle.record(key, success, Utc::now());
+        save_login_throttle(app, &throttle)?;
+        Ok(status)
+    }
+
+    /// Check `key` and reserve the attempt as a failure; settle it with `succeed` or `cancel`
+    pub fn begin(&self, app: &tauri::AppHandle, key: &str) -> AppResult<()> {
+        let _file = self.file.lock().unwrap();
+        let mut throttle = load_login_throttle(app)?;
+        throttle.begin(key, Utc::now())?;
+        save_login_throttle(app, &throttle)
+    }
+
+    /// Settle an attempt reserved by `begin` that succeeded
+    pub fn succeed(&self, app: &tauri::AppHandle, key: &str) -> AppResult<()> {
+        let _file = self.file.lock().unwrap();
+        let mut throttle = load_login_throttle(app)?;
+        throttle.cancel(key);
+        throttle.record(key, true, Utc::now());
+        save_login_throttle(app, &throttle)
+    }
+
+    /// Settle an attempt reserved by `begin` that failed for reasons other than the credentials
+    pub fn cancel(&self, app: &tauri::AppHandle, key: &str) -> AppResult<()> {
+        let _file = self.file.lock().unwrap();
+        let mut throttle = load_login_throttle(app)?;
+        throttle.cancel(key);
+        save_login_throttle(app, &throttle)
+    }
+
+    /// Clear lockouts for one key, or for everyone when `None`
+    pub fn clear(&self, app: &tauri::AppHandle, key: Option<&str>) -> AppResult<()> {
+        let _file = self.file.lock().unwrap();
+        let mut throttle = load_login_throttle(app)?;
+        throttle.clear(key);
+        save_login_throttle(app, &throttle)
+    }
+}
+
+/// Load attempt counters from disk
+fn load_login_throttle(app: &tauri::AppHandle) -> AppResult<LoginThrottle> {
+    let path = app_data_file(app, LOGIN_ATTEMPTS_FILE_NAME)?;
+    Ok(read_yaml(&path)?.unwrap_or_default())
+}
+
+/// Save attempt counters to disk
+fn save_login_throttle(app: &tauri::AppHandle, throttle: &LoginThrottle) -> AppResult<()> {
+    let path = app_data_file(app, LOGIN_ATTEMPTS_FILE_NAME)?;
+    write_yaml(&path, throttle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(secs: i64) -> DateTime<Utc> {
+        Utc.timestamp_opt(1_700_000_000 + secs, 0).unwrap()
+    }
+
+    #[test]
+    fn test_backoff_grows_exponentially() {
+        let mut throttle = LoginThrottle::default();
+        for _ in 0..3 {
+            throttle.record("Teacher", false, at(0));
+        }
+        assert!(throttle.check("teacher", at(0)).is_ok());
+
+        let status = throttle.record("teacher", false, at(0));
+        assert_eq!(status.retry_after_secs, Some(2));
+        let status = throttle.record("teacher", false, at(0));
+        assert_eq!(status.retry_after_secs, Some(4));
+
+        let err = throttle.check(" TEACHER ", at(1)).unwrap_err();
+        assert!(matches!(err.kind, AuthErrorKind::TooManyAttempts));
+        assert_eq!(err.retry_after_secs, Some(3));
+        assert!(throttle.check("teacher", at(4)).is_ok());
+    }
+
+    #[test]
+    fn test_lockout_and_clear() {
+        let mut throttle = LoginThrottle::default();
+        let mut status = throttle.record("student", false, at(0));
+        for _ in 1..ThrottlePolicy::PER_USER.lockout_threshold {
+            status = throttle.record("student", false, at(0));
+        }
+        assert!(status.locked);
+
+        let err = throttle.check("student", at(60)).unwrap_err();
+        assert!(matches!(err.kind, AuthErrorKind::AccountLocked));
+
+        throttle.clear(Some("student"));
+        assert!(throttle.check("student", at(60)).is_ok());
+    }
+
+    #[test]
+    fn test_success_resets_user_counter() {
+        let mut throttle = LoginThrottle::default();
+        for _ in 0..5 {
+            throttle.record("admin", false, at(0));
+        }
+        throttle.record("admin", true, at(10));
+        assert!(!throttle.users.contains_key("admin"));
+        assert!(throttle.check("admin", at(10)).is_ok());
+    }
+
+    #[test]
+    fn test_pending_attempts_count_until_settled() {
+        let mut throttle = LoginThrottle::default();
+        for _ in 0..ThrottlePolicy::PER_USER.free_attempts + 1 {
+            throttle.begin("teacher", at(0)).unwrap();
+        }
+        // Attempts still in flight already hold back the next one
+        assert!(throttle.begin("teacher", at(0)).is_err());
+
+        for _ in 0..ThrottlePolicy::PER_USER.free_attempts + 1 {
+            throttle.cancel("teacher");
+        }
+        assert!(!throttle.users.contains_key("teacher"));
+        assert_eq!(throttle.global.failures, 0);
+        assert!(throttle.begin("teacher", at(0)).is_ok());
+    }
+}
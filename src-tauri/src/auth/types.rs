@@ -1,7 +1,13 @@
 //! Authentication Types
 //! Data structures for authentication
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use super::password::PasswordViolation;
+use crate::common::{AppError, ErrorCode, ErrorKind};
+use crate::device::{DeviceContext, DeviceInfo};
 
 /// User role types
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -13,18 +19,162 @@ pub enum UserRoleType {
     Student,
 }
 
+impl UserRoleType {
+    /// Whether the role can administer this deployment
+    pub fn is_admin(&self) -> bool {
+        matches!(self, UserRoleType::SuperAdmin | UserRoleType::Admin)
+    }
+}
+
+/// User account status
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum UserStatus {
+    #[default]
+    Active,
+    Inactive,
+    Locked,
+    Pending,
+}
+
+/// Permission actions
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PermissionAction {
+    Create,
+    Read,
+    Update,
+    Delete,
+    /// Implies every other action on the resource
+    Manage,
+    Approve,
+    Export,
+    Import,
+}
+
+/// Permission granted (or denied) on a resource
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UserPermission {
+    pub permission_id: String,
+    /// Resource code, e.g. "CLASS" or "GRADE"
+    pub resource: String,
+    pub action: PermissionAction,
+    pub allowed: bool,
+    /// Server-defined conditions, e.g. restricting to the user's own classes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conditions: Option<serde_json::Map<String, serde_json::Value>>,
+}
+
+impl UserPermission {
+    fn matches(&self, resource: &str, action: PermissionAction) -> bool {
+        self.resource.eq_ignore_ascii_case(resource)
+            && (self.action == action || self.action == PermissionAction::Manage)
+    }
+}
+
+/// Role definition
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Role {
+    pub role_id: String,
+    pub role_name: String,
+    pub role_code: String,
+    pub description: String,
+    pub is_system_role: bool,
+    pub is_default: bool,
+    pub priority: i32,
+}
+
 /// Authenticated user information
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Mirrors the frontend `AuthUser` JSON
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct AuthUser {
-    pub id: String,
+    #[serde(alias = "id")]
+    pub user_id: String,
     pub username: String,
-    pub email: Option<String>,
-    pub full_name: Option<String>,
+    #[serde(default)]
+    pub email: String,
+    #[serde(default)]
+    pub full_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub avatar_url: Option<String>,
     pub role_type: UserRoleType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub student_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub teacher_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub admin_id: Option<String>,
+    /// Homeroom class for students
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub class_id: Option<String>,
+    /// Department for teachers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub department: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<UserStatus>,
+    #[serde(default)]
+    pub permissions: Vec<UserPermission>,
+    /// Role codes, e.g. "TEACHER"
+    #[serde(default)]
+    pub roles: Vec<String>,
+}
+
+impl AuthUser {
+    /// Whether the user may perform `action` on `resource`
+    ///
+    /// An explicit deny wins over any grant. Conditions are not evaluated
+    /// here; they are enforced by the school API.
+    pub fn has_permission(&self, resource: &str, action: PermissionAction) -> bool {
+        let mut matching = self
+            .permissions
+            .iter()
+            .filter(|p| p.matches(resource, action))
+            .peekable();
+
+        matching.peek().is_some() && matching.all(|p| p.allowed)
+    }
+
+    /// Whether the user holds a role, by role code
+    pub fn has_role(&self, role_code: &str) -> bool {
+        self.roles.iter().any(|r| r.eq_ignore_ascii_case(role_code))
+    }
+
+    /// Whether the account may be used; a missing status is treated as active
+    pub fn is_active(&self) -> bool {
+        self.status.unwrap_or_default() == UserStatus::Active
+    }
+}
+
+/// Saved account for one-click selection on the login window
+///
+/// Only profile data is kept here; tokens are never stored with it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SavedAccount {
+    pub user_id: String,
+    pub username: String,
+    pub full_name: String,
     pub avatar_url: Option<String>,
-    pub permissions: Vec<String>,
-    pub created_at: Option<String>,
-    pub updated_at: Option<String>,
+    /// Locally cached avatar (data URL) so the picker works offline
+    #[serde(default)]
+    pub avatar_cache: Option<String>,
+    pub role_type: UserRoleType,
+    #[serde(default)]
+    pub last_login: Option<DateTime<Utc>>,
+}
+
+/// Payload for starting a session after a successful sign-in
+///
+/// The user and tokens are not part of the payload; they come from the
+/// school API's answer to `login` for `user_id`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StartSessionRequest {
+    pub user_id: String,
+    /// Password used to sign in, kept only as a hash for unlocking
+    pub password: Option<String>,
+    /// Card id used to sign in, kept only as a hash for unlocking
+    pub card_uid: Option<String>,
+    /// Refresh token lifetime in seconds
+    pub refresh_expires_in: Option<u64>,
 }
 
 /// Login request payload
@@ -33,6 +183,19 @@ pub struct LoginRequest {
     pub username: String,
     pub password: String,
     pub remember_me: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device_info: Option<DeviceInfo>,
+}
+
+impl LoginRequest {
+    /// Attach this device's id and info
+    pub fn with_device(mut self, device: DeviceContext) -> Self {
+        self.device_id = Some(device.device_id);
+        self.device_info = Some(device.device_info);
+        self
+    }
 }
 
 /// Login response
@@ -44,6 +207,14 @@ pub struct LoginResponse {
     pub expires_in: u64,
 }
 
+/// Token refresh request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefreshTokenRequest {
+    pub refresh_token: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device_id: Option<String>,
+}
+
 /// Token refresh response
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RefreshTokenResponse {
@@ -52,6 +223,60 @@ pub struct RefreshTokenResponse {
     pub expires_in: u64,
 }
 
+/// Refresh token revocation sent on logout
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RevokeTokenRequest {
+    pub refresh_token: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub device_id: Option<String>,
+}
+
+/// Password change payload sent to the school API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangePasswordRequest {
+    pub current_password: String,
+    pub new_password: String,
+}
+
+/// Forgot-password payload; the school is notified to reset the account
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PasswordResetRequest {
+    pub username: String,
+}
+
+/// Completes a reset with the token issued by the school
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfirmPasswordResetRequest {
+    pub token: String,
+    pub new_password: String,
+}
+
+/// Credential used to resume a locked session
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum UnlockMethod {
+    Password,
+    Card,
+    Pin,
+}
+
+impl UnlockMethod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UnlockMethod::Password => "password",
+            UnlockMethod::Card => "card",
+            UnlockMethod::Pin => "pin",
+        }
+    }
+}
+
+/// PIN availability for the current session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PinStatus {
+    pub enabled: bool,
+    pub attempts_remaining: u32,
+}
+
 /// Auth error types
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum AuthErrorKind {
@@ -60,6 +285,17 @@ pub enum AuthErrorKind {
     TokenInvalid,
     UserNotFound,
     UserDisabled,
+    /// Too many failed attempts; retry after a backoff delay
+    TooManyAttempts,
+    /// Locked out after repeated failures until the lockout expires or an admin clears it
+    AccountLocked,
+    PermissionDenied,
+    /// A verified MFA code is required before the session can start
+    MfaRequired,
+    /// Policy requires MFA for the user's role but no authenticator is set up yet
+    MfaEnrollmentRequired,
+    /// The new password does not meet the password policy
+    WeakPassword,
     NetworkError,
     Unknown,
 }
@@ -69,6 +305,15 @@ pub enum AuthErrorKind {
 pub struct AuthError {
     pub kind: AuthErrorKind,
     pub message: String,
+    /// Seconds until another attempt is allowed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_after_secs: Option<u64>,
+    /// Password policy rules that failed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub violations: Vec<PasswordViolation>,
+    /// Storage or API error behind this one, kept for the log
+    #[serde(skip)]
+    pub source: Option<Arc<AppError>>,
 }
 
 impl std::fmt::Display for AuthError {
@@ -77,4 +322,178 @@ impl std::fmt::Display for AuthError {
     }
 }
 
-impl std::error::Error for AuthError {}
+impl std::error::Error for AuthError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.source
+            .as_deref()
+            .map(|source| source as &(dyn std::error::Error + 'static))
+    }
+}
+
+impl AuthError {
+    pub fn new(kind: AuthErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            retry_after_secs: None,
+            violations: Vec::new(),
+            source: None,
+        }
+    }
+
+    pub fn with_retry_after(mut self, secs: u64) -> Self {
+        self.retry_after_secs = Some(secs);
+        self
+    }
+
+    pub fn weak_password(violations: Vec<PasswordViolation>) -> Self {
+        Self {
+            violations,
+            ..Self::new(
+                AuthErrorKind::WeakPassword,
+                "Password does not meet the password policy",
+            )
+        }
+    }
+}
+
+// Storage and API failures inside auth commands surface as auth errors
+impl From<AppError> for AuthError {
+    fn from(err: AppError) -> Self {
+        let kind = match err.kind {
+            ErrorKind::Network => AuthErrorKind::NetworkError,
+            ErrorKind::Permission => AuthErrorKind::PermissionDenied,
+            ErrorKind::Auth => AuthErrorKind::TokenInvalid,
+            _ => AuthErrorKind::Unknown,
+        };
+        let message = err.message.clone();
+        Self {
+            source: Some(Arc::new(err)),
+            ..Self::new(kind, message)
+        }
+    }
+}
+
+impl AuthErrorKind {
+    fn code(&self) -> ErrorCode {
+        match self {
+            AuthErrorKind::InvalidCredentials => ErrorCode::AuthInvalidCredentials,
+            AuthErrorKind::TokenExpired => ErrorCode::AuthTokenExpired,
+            AuthErrorKind::TokenInvalid => ErrorCode::AuthTokenInvalid,
+            AuthErrorKind::UserNotFound => ErrorCode::AuthUserNotFound,
+            AuthErrorKind::UserDisabled => ErrorCode::AuthUserDisabled,
+            AuthErrorKind::TooManyAttempts => ErrorCode::AuthTooManyAttempts,
+            AuthErrorKind::AccountLocked => ErrorCode::AuthAccountLocked,
+            AuthErrorKind::PermissionDenied => ErrorCode::AuthPermissionDenied,
+            AuthErrorKind::MfaRequired => ErrorCode::AuthMfaRequired,
+            AuthErrorKind::MfaEnrollmentRequired => ErrorCode::AuthMfaEnrollmentRequired,
+            AuthErrorKind::WeakPassword => ErrorCode::AuthWeakPassword,
+            AuthErrorKind::NetworkError => ErrorCode::AuthNetworkError,
+            AuthErrorKind::Unknown => ErrorCode::AuthUnknown,
+        }
+    }
+}
+
+impl From<AuthError> for AppError {
+    fn from(err: AuthError) -> Self {
+        let kind = match err.kind {
+            AuthErrorKind::UserNotFound => ErrorKind::NotFound,
+            AuthErrorKind::PermissionDenied => ErrorKind::Permission,
+            AuthErrorKind::WeakPassword => ErrorKind::InvalidInput,
+            AuthErrorKind::NetworkError => ErrorKind::Network,
+            AuthErrorKind::Unknown => ErrorKind::Unknown,
+            _ => ErrorKind::Auth,
+        };
+
+        let mut app_error = AppError::new(kind, err.message).with_code(err.kind.code());
+        if let Some(secs) = err.retry_after_secs {
+            app_error = app_error.with_retry_after(secs);
+        }
+        if !err.violations.is_empty() {
+            app_error = app_error.with_data(serde_json::json!({ "violations": err.violations }));
+        }
+        // Skip the auth wrapper so the log shows the original error directly
+        if let Some(source) = err.source {
+            app_error.source = Some(source);
+        }
+        app_error
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    const FIXTURES: [&str; 3] = [
+        include_str!("fixtures/super_admin_user.json"),
+        include_str!("fixtures/teacher_user.json"),
+        include_str!("fixtures/student_user.json"),
+    ];
+
+    fn fixture(index: usize) -> AuthUser {
+        serde_json::from_str(FIXTURES[index]).unwrap()
+    }
+
+    #[test]
+    fn test_auth_user_round_trips_frontend_json() {
+        for json in FIXTURES {
+            let expected: Value = serde_json::from_str(json).unwrap();
+            let user: AuthUser = serde_json::from_str(json).unwrap();
+            assert_eq!(serde_json::to_value(&user).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_role_context_fields() {
+        let teacher = fixture(1);
+        assert_eq!(teacher.role_type, UserRoleType::Teacher);
+        assert_eq!(teacher.teacher_id.as_deref(), Some("teacher_001"));
+        assert_eq!(teacher.department.as_deref(), Some("Toán học"));
+        assert!(teacher.is_active());
+
+        let conditions = teacher.permissions[1].conditions.as_ref().unwrap();
+        assert_eq!(conditions["own_classes_only"], Value::Bool(true));
+
+        let student = fixture(2);
+        assert_eq!(student.class_id.as_deref(), Some("class_10A1"));
+        assert!(student.has_role("student"));
+    }
+
+    #[test]
+    fn test_has_permission() {
+        let admin = fixture(0);
+        assert!(admin.has_permission("system", PermissionAction::Delete));
+        assert!(!admin.has_permission("GRADE", PermissionAction::Read));
+
+        let teacher = fixture(1);
+        assert!(teacher.has_permission("CLASS", PermissionAction::Update));
+        assert!(!teacher.has_permission("GRADE", PermissionAction::Delete));
+        assert!(!teacher.has_permission("CLASS", PermissionAction::Delete));
+    }
+
+    #[test]
+    fn test_auth_errors_convert_to_app_errors() {
+        let locked: AppError = AuthError::new(AuthErrorKind::AccountLocked, "Locked")
+            .with_retry_after(900)
+            .into();
+        assert_eq!(locked.kind, ErrorKind::Auth);
+        assert_eq!(locked.code, ErrorCode::AuthAccountLocked);
+        assert!(locked.retryable);
+        assert_eq!(locked.retry_after_secs, Some(900));
+
+        let weak: AppError = AuthError::weak_password(vec![PasswordViolation::Breached]).into();
+        assert_eq!(weak.kind, ErrorKind::InvalidInput);
+        assert!(!weak.retryable);
+        assert_eq!(weak.data.unwrap()["violations"][0]["rule"], "breached");
+    }
+
+    #[test]
+    fn test_legacy_id_field_is_accepted() {
+        let user: AuthUser =
+            serde_json::from_str(r#"{"id": "u1", "username": "legacy", "role_type": "ADMIN"}"#)
+                .unwrap();
+        assert_eq!(user.user_id, "u1");
+        assert!(user.permissions.is_empty());
+    }
+}
@@ -0,0 +1,77 @@
+//! Session Vault
+//! Encrypts the stored session with a per-install key kept in the OS keychain
+
+use chacha20poly1305::aead::{Aead, KeyInit};
+use chacha20poly1305::{XChaCha20Poly1305, XNonce};
+use rand::RngCore;
+
+use crate::common::{keychain_get, keychain_set, AppResult};
+
+/// Keychain entry holding the hex-encoded session key
+const SESSION_KEY_NAME: &str = "session-key";
+
+/// XChaCha20 nonce length in bytes
+const NONCE_LEN: usize = 24;
+
+pub type VaultKey = [u8; 32];
+
+/// Encrypt and authenticate `plaintext`, returning hex `nonce || ciphertext`
+pub fn seal(key: &VaultKey, plaintext: &[u8]) -> String {
+    let mut nonce = [0u8; NONCE_LEN];
+    rand::thread_rng().fill_bytes(&mut nonce);
+
+    let ciphertext = XChaCha20Poly1305::new(key.into())
+        .encrypt(XNonce::from_slice(&nonce), plaintext)
+        .expect("encrypting in memory cannot fail");
+    hex::encode([nonce.as_slice(), &ciphertext].concat())
+}
+
+/// Decrypt `seal` output; None if the key is wrong or the data was modified
+pub fn open(key: &VaultKey, sealed: &str) -> Option<Vec<u8>> {
+    let bytes = hex::decode(sealed).ok()?;
+    if bytes.len() < NONCE_LEN {
+        return None;
+    }
+    let (nonce, ciphertext) = bytes.split_at(NONCE_LEN);
+    XChaCha20Poly1305::new(key.into())
+        .decrypt(XNonce::from_slice(nonce), ciphertext)
+        .ok()
+}
+
+/// Session key from the keychain, None if this OS account has none yet
+pub fn load_session_key(app: &tauri::AppHandle) -> AppResult<Option<VaultKey>> {
+    Ok(keychain_get(app, SESSION_KEY_NAME)?
+        .and_then(|hex_key| hex::decode(hex_key).ok())
+        .and_then(|bytes| bytes.try_into().ok()))
+}
+
+/// Session key from the keychain, generating and storing one on first use
+pub fn session_key(app: &tauri::AppHandle) -> AppResult<VaultKey> {
+    if let Some(key) = load_session_key(app)? {
+        return Ok(key);
+    }
+
+    let mut key = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut key);
+    keychain_set(app, SESSION_KEY_NAME, &hex::encode(key))?;
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sealed_data_needs_the_same_key() {
+        let key = [7u8; 32];
+        let sealed = seal(&key, b"refresh-token");
+        assert_eq!(open(&key, &sealed).unwrap(), b"refresh-token");
+        assert!(open(&[8u8; 32], &sealed).is_none());
+
+        // Any modification is detected
+        let mut tampered = hex::decode(&sealed).unwrap();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(open(&key, &hex::encode(tampered)).is_none());
+        assert!(open(&key, "00").is_none());
+    }
+}
@@ -1,11 +1,20 @@
 //! Common Error Types
 //! Centralized error handling for the application
 
+use log::Level;
 use serde::{Deserialize, Serialize};
+use std::backtrace::Backtrace;
+use std::collections::BTreeMap;
+use std::error::Error;
 use std::fmt;
+use std::sync::Arc;
+use tauri::ipc::InvokeError;
+
+use super::error_catalog::{error_language, localize};
+use super::error_code::ErrorCode;
 
 /// Application error kinds
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum ErrorKind {
     /// Configuration error
@@ -20,6 +29,8 @@ pub enum ErrorKind {
     Auth,
     /// Permission denied
     Permission,
+    /// Network or remote API error
+    Network,
     /// Resource not found
     NotFound,
     /// Invalid input
@@ -28,25 +39,122 @@ pub enum ErrorKind {
     Unknown,
 }
 
-/// Application-wide error type
-#[derive(Debug, Clone, Serialize, Deserialize)]
+impl ErrorKind {
+    /// Code used when nothing more specific is known, e.g. `NETWORK`
+    pub fn default_code(self) -> ErrorCode {
+        match self {
+            ErrorKind::Config => ErrorCode::Config,
+            ErrorKind::Io => ErrorCode::Io,
+            ErrorKind::Serialization => ErrorCode::Serialization,
+            ErrorKind::Window => ErrorCode::Window,
+            ErrorKind::Auth => ErrorCode::Auth,
+            ErrorKind::Permission => ErrorCode::Permission,
+            ErrorKind::Network => ErrorCode::Network,
+            ErrorKind::NotFound => ErrorCode::NotFound,
+            ErrorKind::InvalidInput => ErrorCode::InvalidInput,
+            ErrorKind::Unknown => ErrorCode::Unknown,
+        }
+    }
+
+    /// Whether errors of this kind are usually transient
+    pub fn retryable(self) -> bool {
+        matches!(self, ErrorKind::Network)
+    }
+
+    /// Level failures of this kind are logged at; expected user mistakes are only warnings
+    pub fn log_level(self) -> Level {
+        match self {
+            ErrorKind::Auth
+            | ErrorKind::Permission
+            | ErrorKind::NotFound
+            | ErrorKind::InvalidInput => Level::Warn,
+            _ => Level::Error,
+        }
+    }
+}
+
+/// Application-wide error type, returned by every fallible command
+///
+/// Converting an error into an `InvokeError` is the IPC boundary: when a
+/// command fails, the full error with its source chain is logged once under a
+/// new correlation id, and only a safe summary
+/// `{ kind, code, message, retryable, retry_after_secs?, params?, data?, correlation_id }`
+/// is sent, where `message` is the catalog message for `code` in `config.language`.
+#[derive(Debug, Clone)]
 pub struct AppError {
     pub kind: ErrorKind,
+    /// Stable machine-readable code, e.g. `AUTH_TOO_MANY_ATTEMPTS`
+    pub code: ErrorCode,
+    /// English message for logs; generic codes also show it to the user
     pub message: String,
-    pub details: Option<String>,
+    /// Extra context for the log, e.g. the HTTP status
+    pub details: Option<Box<str>>,
+    /// Whether the same request can succeed if tried again
+    pub retryable: bool,
+    /// Seconds to wait before trying again
+    pub retry_after_secs: Option<u64>,
+    /// Values for the placeholders of the code's message, e.g. `theme`
+    pub params: BTreeMap<String, String>,
+    /// Structured context for the frontend, e.g. the password rules that failed
+    pub data: Option<Box<serde_json::Value>>,
+    /// Underlying error; logged, never sent to the frontend
+    pub source: Option<Arc<dyn Error + Send + Sync>>,
+    /// Where the error was created, captured in debug builds only
+    pub backtrace: Option<Arc<Backtrace>>,
 }
 
 impl AppError {
     pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
         Self {
             kind,
+            code: kind.default_code(),
             message: message.into(),
             details: None,
+            retryable: kind.retryable(),
+            retry_after_secs: None,
+            params: BTreeMap::new(),
+            data: None,
+            source: None,
+            backtrace: cfg!(debug_assertions).then(|| Arc::new(Backtrace::force_capture())),
         }
     }
 
     pub fn with_details(mut self, details: impl Into<String>) -> Self {
-        self.details = Some(details.into());
+        self.details = Some(details.into().into_boxed_str());
+        self
+    }
+
+    /// Keep the error that caused this one for the log
+    pub fn with_source(mut self, source: impl Error + Send + Sync + 'static) -> Self {
+        self.source = Some(Arc::new(source));
+        self
+    }
+
+    pub fn with_code(mut self, code: ErrorCode) -> Self {
+        self.code = code;
+        self
+    }
+
+    /// Value for a `{name}` placeholder in the code's message
+    pub fn with_param(mut self, name: &str, value: impl ToString) -> Self {
+        self.params.insert(name.to_string(), value.to_string());
+        self
+    }
+
+    pub fn with_retryable(mut self, retryable: bool) -> Self {
+        self.retryable = retryable;
+        self
+    }
+
+    /// Retryable after a delay
+    pub fn with_retry_after(mut self, secs: u64) -> Self {
+        self.retryable = true;
+        self.retry_after_secs = Some(secs);
+        self
+    }
+
+    pub fn with_data(mut self, data: serde_json::Value) -> Self {
+        self.data = Some(Box::new(data));
         self
     }
 
@@ -66,6 +174,14 @@ impl AppError {
         Self::new(ErrorKind::Auth, message)
     }
 
+    pub fn network(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Network, message)
+    }
+
+    pub fn permission(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Permission, message)
+    }
+
     pub fn not_found(message: impl Into<String>) -> Self {
         Self::new(ErrorKind::NotFound, message)
     }
@@ -73,6 +189,81 @@ impl AppError {
     pub fn invalid_input(message: impl Into<String>) -> Self {
         Self::new(ErrorKind::InvalidInput, message)
     }
+
+    /// Message for the user in a language from the error catalog
+    pub fn localized_message(&self, language: &str) -> String {
+        let mut params = self.params.clone();
+        params.insert("message".to_string(), self.message.clone());
+        if let Some(secs) = self.retry_after_secs {
+            params.insert("retry_after_secs".to_string(), secs.to_string());
+        }
+        localize(self.code, language, &params)
+    }
+
+    /// This error's source, its source's source, and so on
+    pub fn chain(&self) -> impl Iterator<Item = &(dyn Error + 'static)> {
+        std::iter::successors(self.source(), |&err| err.source())
+    }
+
+    /// Log the error, its source chain and (in debug builds) the backtrace
+    pub fn log(&self, correlation_id: &str) {
+        let mut report = format!("[{}] {:?} {}", correlation_id, self.code, self);
+        for cause in self.chain() {
+            report.push_str(&format!("\n  caused by: {}", cause));
+        }
+        if let Some(backtrace) = &self.backtrace {
+            report.push_str(&format!("\nbacktrace:\n{}", backtrace));
+        }
+        log::log!(self.kind.log_level(), "{}", report);
+    }
+}
+
+/// Short random id that ties an error shown to the user to its log entry
+pub fn new_correlation_id() -> String {
+    let mut id = uuid::Uuid::new_v4().simple().to_string();
+    id.truncate(12);
+    id
+}
+
+/// Safe summary of an `AppError` sent to the frontend
+#[derive(Serialize)]
+struct AppErrorPayload<'a> {
+    kind: ErrorKind,
+    code: ErrorCode,
+    message: String,
+    retryable: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    retry_after_secs: Option<u64>,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    params: &'a BTreeMap<String, String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: &'a Option<Box<serde_json::Value>>,
+    correlation_id: String,
+}
+
+impl AppError {
+    fn payload(&self, correlation_id: String) -> AppErrorPayload<'_> {
+        AppErrorPayload {
+            kind: self.kind,
+            code: self.code,
+            message: self.localized_message(&error_language()),
+            retryable: self.retryable,
+            retry_after_secs: self.retry_after_secs,
+            params: &self.params,
+            data: &self.data,
+            correlation_id,
+        }
+    }
+}
+
+/// A failed command: the error is logged once under a new correlation id,
+/// and the frontend gets the safe summary carrying the same id
+impl From<AppError> for InvokeError {
+    fn from(err: AppError) -> Self {
+        let correlation_id = new_correlation_id();
+        err.log(&correlation_id);
+        InvokeError::from(err.payload(correlation_id))
+    }
 }
 
 impl fmt::Display for AppError {
@@ -84,21 +275,135 @@ impl fmt::Display for AppError {
     }
 }
 
-impl std::error::Error for AppError {}
+impl Error for AppError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source
+            .as_deref()
+            .map(|source| source as &(dyn Error + 'static))
+    }
+}
 
 // Convert from std::io::Error
 impl From<std::io::Error> for AppError {
     fn from(err: std::io::Error) -> Self {
-        Self::io(err.to_string())
+        Self::io(format!("File operation failed: {}", err.kind())).with_source(err)
     }
 }
 
-// Convert to String for Tauri commands
-impl From<AppError> for String {
-    fn from(err: AppError) -> Self {
-        err.to_string()
+impl From<tauri::Error> for AppError {
+    fn from(err: tauri::Error) -> Self {
+        match err {
+            tauri::Error::Io(err) => err.into(),
+            tauri::Error::WebviewNotFound | tauri::Error::WindowNotFound => {
+                Self::not_found("Window not found").with_code(ErrorCode::WindowNotFound)
+            }
+            err => Self::window("Window operation failed")
+                .with_code(ErrorCode::WindowOperationFailed)
+                .with_source(err),
+        }
+    }
+}
+
+impl From<serde_json::Error> for AppError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::new(ErrorKind::Serialization, "Invalid JSON")
+            .with_code(ErrorCode::JsonInvalid)
+            .with_source(err)
+    }
+}
+
+impl From<serde_yaml::Error> for AppError {
+    fn from(err: serde_yaml::Error) -> Self {
+        Self::new(ErrorKind::Serialization, "Invalid YAML")
+            .with_code(ErrorCode::YamlInvalid)
+            .with_source(err)
     }
 }
 
 /// Result type alias for app operations
 pub type AppResult<T> = Result<T, AppError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serializes_as_safe_summary() {
+        let err = AppError::network("School API is unreachable")
+            .with_details("timed out")
+            .with_source(std::io::Error::other("connect to 10.0.0.5 failed"));
+        let mut value = InvokeError::from(err).0;
+
+        let correlation_id = value
+            .as_object_mut()
+            .unwrap()
+            .remove("correlation_id")
+            .unwrap();
+        assert_eq!(correlation_id.as_str().unwrap().len(), 12);
+        // Details and the source chain stay in the log
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "kind": "NETWORK",
+                "code": "NETWORK",
+                "message": "School API is unreachable",
+                "retryable": true,
+            })
+        );
+    }
+
+    #[test]
+    fn test_source_chain() {
+        let inner = AppError::from(std::io::Error::new(
+            std::io::ErrorKind::PermissionDenied,
+            "access denied",
+        ));
+        let outer = AppError::config("Could not save").with_source(inner);
+
+        let chain = outer.chain().map(|e| e.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            chain,
+            [
+                "Io: File operation failed: permission denied",
+                "access denied"
+            ]
+        );
+        assert_eq!(outer.backtrace.is_some(), cfg!(debug_assertions));
+    }
+
+    #[test]
+    fn test_yaml_errors_convert() {
+        let err: AppError = serde_yaml::from_str::<Vec<u32>>("[1, x")
+            .unwrap_err()
+            .into();
+        assert_eq!(err.kind, ErrorKind::Serialization);
+        assert_eq!(err.code, ErrorCode::YamlInvalid);
+        assert!(!err.retryable);
+        assert!(err.source.is_some());
+    }
+
+    #[test]
+    fn test_retry_after_marks_retryable() {
+        let err = AppError::auth("Slow down").with_retry_after(30);
+        assert!(err.retryable);
+        assert_eq!(err.retry_after_secs, Some(30));
+    }
+
+    #[test]
+    fn test_localized_message_uses_params() {
+        let err = AppError::config("Invalid theme: neon")
+            .with_code(ErrorCode::ConfigInvalidTheme)
+            .with_param("theme", "neon");
+        assert_eq!(
+            err.localized_message("en"),
+            "\"neon\" is not a theme. Choose dark, light or system."
+        );
+        assert!(err.localized_message("vi").contains("neon"));
+        assert_eq!(err.to_string(), "Config: Invalid theme: neon");
+
+        let throttled = AppError::auth("Too many attempts")
+            .with_code(ErrorCode::AuthTooManyAttempts)
+            .with_retry_after(30);
+        assert!(throttled.localized_message("en").contains("30 seconds"));
+    }
+}
@@ -0,0 +1,329 @@
+//! Error Catalog
+//! Localized message templates for error codes, in the language set by `config.language`
+
+use std::collections::BTreeMap;
+use std::sync::RwLock;
+
+use super::error_code::ErrorCode;
+
+/// Languages every code has a message in
+pub const CATALOG_LANGUAGES: [&str; 2] = ["en", "vi"];
+
+/// Language errors are reported in; empty until the config is loaded
+static ERROR_LANGUAGE: RwLock<String> = RwLock::new(String::new());
+
+/// Report errors in a language from now on; unknown languages fall back to English
+pub fn set_error_language(language: &str) {
+    *ERROR_LANGUAGE.write().unwrap() = language.to_string();
+}
+
+pub fn error_language() -> String {
+    ERROR_LANGUAGE.read().unwrap().clone()
+}
+
+/// English and Vietnamese templates; `{name}` is replaced by the parameter `name`
+///
+/// `{message}` is the error's own message, so generic codes pass it through.
+fn templates(code: ErrorCode) -> (&'static str, &'static str) {
+    match code {
+        ErrorCode::Config => ("{message}", "Lỗi cấu hình: {message}"),
+        ErrorCode::Io => ("{message}", "Lỗi đọc hoặc ghi tệp: {message}"),
+        ErrorCode::Serialization => ("{message}", "Dữ liệu không hợp lệ: {message}"),
+        ErrorCode::Window => ("{message}", "Lỗi cửa sổ: {message}"),
+        ErrorCode::Auth => ("{message}", "Lỗi xác thực: {message}"),
+        ErrorCode::Permission => ("{message}", "Không có quyền: {message}"),
+        ErrorCode::Network => ("{message}", "Lỗi kết nối: {message}"),
+        ErrorCode::NotFound => ("{message}", "Không tìm thấy: {message}"),
+        ErrorCode::InvalidInput => ("{message}", "Dữ liệu nhập không hợp lệ: {message}"),
+        ErrorCode::Unknown => ("{message}", "Đã xảy ra lỗi: {message}"),
+
+        ErrorCode::ConfigDirNotFound => (
+            "The configuration folder could not be found.",
+            "Không tìm thấy thư mục cấu hình.",
+        ),
+        ErrorCode::ConfigReadFailed => (
+            "The configuration file could not be read.",
+            "Không thể đọc tệp cấu hình.",
+        ),
+        ErrorCode::ConfigWriteFailed => (
+            "The configuration file could not be saved.",
+            "Không thể lưu tệp cấu hình.",
+        ),
+        ErrorCode::ConfigParseFailed => (
+            "The configuration file is invalid.",
+            "Tệp cấu hình không hợp lệ.",
+        ),
+        ErrorCode::ConfigInvalidTheme => (
+            "\"{theme}\" is not a theme. Choose dark, light or system.",
+            "\"{theme}\" không phải là giao diện hợp lệ. Hãy chọn tối, sáng hoặc theo hệ thống.",
+        ),
+        ErrorCode::ConfigInvalidScreenMode => (
+            "\"{mode}\" is not a screen mode. Choose normal, maximized or fullscreen.",
+            "\"{mode}\" không phải là chế độ màn hình hợp lệ. Hãy chọn bình thường, phóng to hoặc toàn màn hình.",
+        ),
+        ErrorCode::AppDataDirUnavailable => (
+            "The app data folder is not available.",
+            "Không truy cập được thư mục dữ liệu của ứng dụng.",
+        ),
+        ErrorCode::KeychainUnavailable => (
+            "The system keychain is not available.",
+            "Không truy cập được kho khóa của hệ thống.",
+        ),
+        ErrorCode::FileParseFailed => (
+            "{file} is damaged and could not be read.",
+            "Tệp {file} bị hỏng và không thể đọc.",
+        ),
+        ErrorCode::FileWriteFailed => ("{file} could not be saved.", "Không thể lưu tệp {file}."),
+        ErrorCode::ArchiveWriteFailed => (
+            "The archive could not be created.",
+            "Không thể tạo tệp nén.",
+        ),
+        ErrorCode::YamlInvalid => ("The YAML data is invalid.", "Dữ liệu YAML không hợp lệ."),
+        ErrorCode::JsonInvalid => ("The JSON data is invalid.", "Dữ liệu JSON không hợp lệ."),
+
+        ErrorCode::ApiNotConfigured => (
+            "No school server is configured.",
+            "Chưa cấu hình máy chủ của trường.",
+        ),
+        ErrorCode::ApiUnreachable => (
+            "Could not reach the school server. Check the network connection.",
+            "Không kết nối được máy chủ của trường. Hãy kiểm tra kết nối mạng.",
+        ),
+        ErrorCode::ApiInvalidResponse => (
+            "The school server sent an unexpected response.",
+            "Máy chủ của trường trả về phản hồi không hợp lệ.",
+        ),
+        ErrorCode::ApiRequestRejected => (
+            "The school server rejected the request: {message}",
+            "Máy chủ của trường từ chối yêu cầu: {message}",
+        ),
+        ErrorCode::ApiUnauthorized => (
+            "Your session is no longer valid. Please sign in again.",
+            "Phiên đăng nhập không còn hiệu lực. Vui lòng đăng nhập lại.",
+        ),
+        ErrorCode::ApiForbidden => (
+            "You do not have permission to do this.",
+            "Bạn không có quyền thực hiện thao tác này.",
+        ),
+        ErrorCode::ApiNotFound => (
+            "The school server could not find what was requested.",
+            "Máy chủ của trường không tìm thấy dữ liệu được yêu cầu.",
+        ),
+        ErrorCode::ApiServerError => (
+            "The school server is having problems. Try again later.",
+            "Máy chủ của trường đang gặp sự cố. Vui lòng thử lại sau.",
+        ),
+
+        ErrorCode::AuthInvalidCredentials => (
+            "The username, password or code is incorrect.",
+            "Tên đăng nhập, mật khẩu hoặc mã không đúng.",
+        ),
+        ErrorCode::AuthTokenExpired => (
+            "Your session has expired. Please sign in again.",
+            "Phiên đăng nhập đã hết hạn. Vui lòng đăng nhập lại.",
+        ),
+        ErrorCode::AuthTokenInvalid => (
+            "Your session is invalid. Please sign in again.",
+            "Phiên đăng nhập không hợp lệ. Vui lòng đăng nhập lại.",
+        ),
+        ErrorCode::AuthUserNotFound => ("The account was not found.", "Không tìm thấy tài khoản."),
+        ErrorCode::AuthUserDisabled => (
+            "This account is disabled.",
+            "Tài khoản này đã bị vô hiệu hóa.",
+        ),
+        ErrorCode::AuthTooManyAttempts => (
+            "Too many attempts. Try again in {retry_after_secs} seconds.",
+            "Bạn đã thử quá nhiều lần. Vui lòng thử lại sau {retry_after_secs} giây.",
+        ),
+        ErrorCode::AuthAccountLocked => (
+            "This account is locked. Try again later or ask an administrator to unlock it.",
+            "Tài khoản đã bị khóa. Vui lòng thử lại sau hoặc liên hệ quản trị viên để mở khóa.",
+        ),
+        ErrorCode::AuthPermissionDenied => (
+            "You do not have permission to do this.",
+            "Bạn không có quyền thực hiện thao tác này.",
+        ),
+        ErrorCode::AuthMfaRequired => (
+            "Enter the code from your authenticator app to continue.",
+            "Nhập mã từ ứng dụng xác thực để tiếp tục.",
+        ),
+        ErrorCode::AuthMfaEnrollmentRequired => (
+            "Your role requires an authenticator app. Set one up to finish signing in.",
+            "Vai trò của bạn yêu cầu ứng dụng xác thực. Hãy thiết lập để hoàn tất đăng nhập.",
+        ),
+        ErrorCode::AuthWeakPassword => (
+            "The password does not meet the password policy.",
+            "Mật khẩu không đáp ứng chính sách mật khẩu.",
+        ),
+        ErrorCode::AuthNetworkError => (
+            "Could not reach the sign-in server.",
+            "Không kết nối được máy chủ đăng nhập.",
+        ),
+        ErrorCode::AuthUnknown => ("{message}", "Lỗi tài khoản: {message}"),
+        ErrorCode::SavedAccountNotFound => (
+            "The saved account was not found.",
+            "Không tìm thấy tài khoản đã lưu.",
+        ),
+
+        ErrorCode::DeviceIdInvalid => ("The device id is invalid.", "Mã thiết bị không hợp lệ."),
+        ErrorCode::DeviceChallengeInvalid => (
+            "The device challenge is invalid.",
+            "Yêu cầu xác minh thiết bị không hợp lệ.",
+        ),
+        ErrorCode::DeviceKeyCorrupt => (
+            "This device's key is damaged. Register the device again.",
+            "Khóa của thiết bị bị hỏng. Hãy đăng ký lại thiết bị.",
+        ),
+
+        ErrorCode::WindowNotFound => ("The window is not open.", "Cửa sổ chưa được mở."),
+        ErrorCode::WindowOperationFailed => (
+            "The window could not be updated.",
+            "Không thể cập nhật cửa sổ.",
+        ),
+        ErrorCode::WindowInvalidRoute => (
+            "\"{route}\" cannot be opened in a window.",
+            "Không thể mở \"{route}\" trong cửa sổ.",
+        ),
+        ErrorCode::WindowNotDetachable => (
+            "This window cannot be opened separately.",
+            "Không thể mở riêng cửa sổ này.",
+        ),
+        ErrorCode::KioskActionDisabled => (
+            "This is disabled in kiosk mode.",
+            "Chức năng này bị tắt ở chế độ kiosk.",
+        ),
+        ErrorCode::NoDisplayAvailable => (
+            "No display is available for presenting.",
+            "Không có màn hình nào để trình chiếu.",
+        ),
+        ErrorCode::DisplayNotConnected => (
+            "Display {display} is not connected.",
+            "Màn hình {display} chưa được kết nối.",
+        ),
+        ErrorCode::PresentationNotOpen => (
+            "The presentation window is not open.",
+            "Cửa sổ trình chiếu chưa được mở.",
+        ),
+
+        ErrorCode::ShortcutUnknownAction => (
+            "Unknown shortcut action.",
+            "Không rõ thao tác của phím tắt.",
+        ),
+        ErrorCode::ShortcutInvalid => (
+            "\"{accelerator}\" is not a valid shortcut.",
+            "\"{accelerator}\" không phải là phím tắt hợp lệ.",
+        ),
+        ErrorCode::ShortcutConflict => (
+            "{accelerator} is already used by another shortcut.",
+            "{accelerator} đã được dùng cho một phím tắt khác.",
+        ),
+
+        ErrorCode::LogInvalidLevel => (
+            "\"{level}\" is not a log level.",
+            "\"{level}\" không phải là mức ghi log hợp lệ.",
+        ),
+        ErrorCode::LogDirUnavailable => (
+            "Logging to file is not available.",
+            "Không thể ghi log ra tệp.",
+        ),
+    }
+}
+
+/// Template for a code in a language, English when the language is not in the catalog
+pub fn error_template(code: ErrorCode, language: &str) -> &'static str {
+    let (en, vi) = templates(code);
+    match language {
+        "vi" => vi,
+        _ => en,
+    }
+}
+
+/// Replace `{name}` placeholders; placeholders without a parameter are kept
+fn render(template: &str, params: &BTreeMap<String, String>) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        match after.find('}') {
+            Some(end) if params.contains_key(&after[..end]) => {
+                out.push_str(&params[&after[..end]]);
+                rest = &after[end + 1..];
+            }
+            _ => {
+                out.push('{');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Message for a code in a language, with parameters filled in
+pub fn localize(code: ErrorCode, language: &str, params: &BTreeMap<String, String>) -> String {
+    render(error_template(code, language), params)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    fn placeholders(template: &str) -> BTreeSet<&str> {
+        template
+            .split('{')
+            .skip(1)
+            .filter_map(|part| part.split_once('}').map(|(name, _)| name))
+            .collect()
+    }
+
+    #[test]
+    fn test_every_code_is_translated() {
+        let unique = ErrorCode::ALL.iter().collect::<BTreeSet<_>>();
+        assert_eq!(unique.len(), ErrorCode::ALL.len(), "duplicate code in ALL");
+
+        for code in ErrorCode::ALL {
+            let english = error_template(code, "en");
+            assert!(!english.is_empty(), "{:?} has no English message", code);
+
+            for language in CATALOG_LANGUAGES {
+                let template = error_template(code, language);
+                assert!(
+                    !template.trim().is_empty(),
+                    "{:?} has no {} message",
+                    code,
+                    language
+                );
+                assert_eq!(
+                    placeholders(template),
+                    placeholders(english),
+                    "{:?} in {} uses different parameters",
+                    code,
+                    language
+                );
+            }
+            assert_ne!(
+                error_template(code, "vi"),
+                english,
+                "{:?} is not translated to vi",
+                code
+            );
+        }
+    }
+
+    #[test]
+    fn test_localize_fills_parameters() {
+        let params = BTreeMap::from([("theme".to_string(), "neon".to_string())]);
+        assert_eq!(
+            localize(ErrorCode::ConfigInvalidTheme, "en", &params),
+            "\"neon\" is not a theme. Choose dark, light or system."
+        );
+        assert!(localize(ErrorCode::ConfigInvalidTheme, "vi", &params).starts_with("\"neon\""));
+        // Unknown languages fall back to English; missing parameters stay visible
+        assert_eq!(
+            localize(ErrorCode::DisplayNotConnected, "fr", &BTreeMap::new()),
+            "Display {display} is not connected."
+        );
+    }
+}
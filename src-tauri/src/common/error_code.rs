@@ -0,0 +1,156 @@
+//! Error Codes
+//! Stable machine-readable codes sent with every error; the frontend switches on these
+
+use serde::{Deserialize, Serialize};
+
+/// Stable error code, serialized as e.g. "AUTH_TOKEN_EXPIRED"
+///
+/// Codes are never renamed or reused; add a new one instead. Every code needs
+/// a message in each language of the catalog.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    // Generic codes, one per error kind
+    Config,
+    Io,
+    Serialization,
+    Window,
+    Auth,
+    Permission,
+    Network,
+    NotFound,
+    InvalidInput,
+    Unknown,
+
+    // Configuration and local files
+    ConfigDirNotFound,
+    ConfigReadFailed,
+    ConfigWriteFailed,
+    ConfigParseFailed,
+    ConfigInvalidTheme,
+    ConfigInvalidScreenMode,
+    AppDataDirUnavailable,
+    KeychainUnavailable,
+    FileParseFailed,
+    FileWriteFailed,
+    ArchiveWriteFailed,
+    YamlInvalid,
+    JsonInvalid,
+
+    // School API
+    ApiNotConfigured,
+    ApiUnreachable,
+    ApiInvalidResponse,
+    ApiRequestRejected,
+    ApiUnauthorized,
+    ApiForbidden,
+    ApiNotFound,
+    ApiServerError,
+
+    // Authentication
+    AuthInvalidCredentials,
+    AuthTokenExpired,
+    AuthTokenInvalid,
+    AuthUserNotFound,
+    AuthUserDisabled,
+    AuthTooManyAttempts,
+    AuthAccountLocked,
+    AuthPermissionDenied,
+    AuthMfaRequired,
+    AuthMfaEnrollmentRequired,
+    AuthWeakPassword,
+    AuthNetworkError,
+    AuthUnknown,
+    SavedAccountNotFound,
+
+    // Devices
+    DeviceIdInvalid,
+    DeviceKeyCorrupt,
+    DeviceChallengeInvalid,
+
+    // Windows
+    WindowNotFound,
+    WindowOperationFailed,
+    WindowInvalidRoute,
+    WindowNotDetachable,
+    KioskActionDisabled,
+    NoDisplayAvailable,
+    DisplayNotConnected,
+    PresentationNotOpen,
+
+    // Shortcuts
+    ShortcutUnknownAction,
+    ShortcutInvalid,
+    ShortcutConflict,
+
+    // Logging
+    LogInvalidLevel,
+    LogDirUnavailable,
+}
+
+impl ErrorCode {
+    /// Every code, for catalog checks
+    pub const ALL: [ErrorCode; 61] = [
+        ErrorCode::Config,
+        ErrorCode::Io,
+        ErrorCode::Serialization,
+        ErrorCode::Window,
+        ErrorCode::Auth,
+        ErrorCode::Permission,
+        ErrorCode::Network,
+        ErrorCode::NotFound,
+        ErrorCode::InvalidInput,
+        ErrorCode::Unknown,
+        ErrorCode::ConfigDirNotFound,
+        ErrorCode::ConfigReadFailed,
+        ErrorCode::ConfigWriteFailed,
+        ErrorCode::ConfigParseFailed,
+        ErrorCode::ConfigInvalidTheme,
+        ErrorCode::ConfigInvalidScreenMode,
+        ErrorCode::AppDataDirUnavailable,
+        ErrorCode::KeychainUnavailable,
+        ErrorCode::FileParseFailed,
+        ErrorCode::FileWriteFailed,
+        ErrorCode::ArchiveWriteFailed,
+        ErrorCode::YamlInvalid,
+        ErrorCode::JsonInvalid,
+        ErrorCode::ApiNotConfigured,
+        ErrorCode::ApiUnreachable,
+        ErrorCode::ApiInvalidResponse,
+        ErrorCode::ApiRequestRejected,
+        ErrorCode::ApiUnauthorized,
+        ErrorCode::ApiForbidden,
+        ErrorCode::ApiNotFound,
+        ErrorCode::ApiServerError,
+        ErrorCode::AuthInvalidCredentials,
+        ErrorCode::AuthTokenExpired,
+        ErrorCode::AuthTokenInvalid,
+        ErrorCode::AuthUserNotFound,
+        ErrorCode::AuthUserDisabled,
+        ErrorCode::AuthTooManyAttempts,
+        ErrorCode::AuthAccountLocked,
+        ErrorCode::AuthPermissionDenied,
+        ErrorCode::AuthMfaRequired,
+        ErrorCode::AuthMfaEnrollmentRequired,
+        ErrorCode::AuthWeakPassword,
+        ErrorCode::AuthNetworkError,
+        ErrorCode::AuthUnknown,
+        ErrorCode::SavedAccountNotFound,
+        ErrorCode::DeviceIdInvalid,
+        ErrorCode::DeviceKeyCorrupt,
+        ErrorCode::DeviceChallengeInvalid,
+        ErrorCode::WindowNotFound,
+        ErrorCode::WindowOperationFailed,
+        ErrorCode::WindowInvalidRoute,
+        ErrorCode::WindowNotDetachable,
+        ErrorCode::KioskActionDisabled,
+        ErrorCode::NoDisplayAvailable,
+        ErrorCode::DisplayNotConnected,
+        ErrorCode::PresentationNotOpen,
+        ErrorCode::ShortcutUnknownAction,
+        ErrorCode::ShortcutInvalid,
+        ErrorCode::ShortcutConflict,
+        ErrorCode::LogInvalidLevel,
+        ErrorCode::LogDirUnavailable,
+    ];
+}
@@ -0,0 +1,41 @@
+//! OS Keychain
+//! Small secrets kept in the platform credential store, outside the app data dir
+
+use keyring::Entry;
+
+use super::error::{AppError, AppResult};
+use super::error_code::ErrorCode;
+
+fn entry(app: &tauri::AppHandle, name: &str) -> AppResult<Entry> {
+    Entry::new(&app.config().identifier, name).map_err(keychain_error)
+}
+
+/// Read a secret, returning None if it was never stored
+pub fn keychain_get(app: &tauri::AppHandle, name: &str) -> AppResult<Option<String>> {
+    match entry(app, name)?.get_password() {
+        Ok(value) => Ok(Some(value)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(keychain_error(e)),
+    }
+}
+
+/// Store or replace a secret
+pub fn keychain_set(app: &tauri::AppHandle, name: &str, value: &str) -> AppResult<()> {
+    entry(app, name)?
+        .set_password(value)
+        .map_err(keychain_error)
+}
+
+/// Delete a secret; deleting one that does not exist succeeds
+pub fn keychain_delete(app: &tauri::AppHandle, name: &str) -> AppResult<()> {
+    match entry(app, name)?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(keychain_error(e)),
+    }
+}
+
+fn keychain_error(err: keyring::Error) -> AppError {
+    AppError::io("OS keychain is not available")
+        .with_code(ErrorCode::KeychainUnavailable)
+        .with_source(err)
+}
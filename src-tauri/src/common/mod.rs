@@ -5,7 +5,15 @@
 #![allow(unused_imports)]
 
 mod error;
+mod error_catalog;
+mod error_code;
+mod keychain;
+mod storage;
 mod types;
 
 pub use error::*;
+pub use error_catalog::*;
+pub use error_code::*;
+pub use keychain::*;
+pub use storage::*;
 pub use types::*;
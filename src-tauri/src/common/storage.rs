@@ -0,0 +1,100 @@
+//! Common Storage Helpers
+//! YAML file persistence shared by modules that keep their own state files
+
+use serde::{de::DeserializeOwned, Serialize};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tauri::Manager;
+
+use super::{AppError, AppResult, ErrorCode, ErrorKind};
+
+/// Resolve a file path inside the app data directory
+pub fn app_data_file(app: &tauri::AppHandle, file_name: &str) -> AppResult<PathBuf> {
+    let data_dir = app.path().app_data_dir().map_err(|e| {
+        AppError::io("Failed to get app data directory")
+            .with_code(ErrorCode::AppDataDirUnavailable)
+            .with_source(e)
+    })?;
+
+    Ok(data_dir.join(file_name))
+}
+
+/// File name shown to users, without the data directory
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+/// Read a YAML file, returning None if it does not exist yet
+pub fn read_yaml<T: DeserializeOwned>(path: &Path) -> AppResult<Option<T>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(path)?;
+    let value = serde_yaml::from_str(&content).map_err(|e| {
+        AppError::new(
+            ErrorKind::Serialization,
+            format!("Failed to parse {}", path.display()),
+        )
+        .with_code(ErrorCode::FileParseFailed)
+        .with_param("file", file_name(path))
+        .with_source(e)
+    })?;
+
+    Ok(Some(value))
+}
+
+/// Write a YAML file, creating parent directories as needed
+///
+/// The content is written to a temporary file first and then renamed over
+/// the target so a crash mid-write never leaves a truncated file behind.
+pub fn write_yaml<T: Serialize>(path: &Path, value: &T) -> AppResult<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let yaml = serde_yaml::to_string(value).map_err(|e| {
+        AppError::new(
+            ErrorKind::Serialization,
+            format!("Failed to serialize {}", path.display()),
+        )
+        .with_code(ErrorCode::FileWriteFailed)
+        .with_param("file", file_name(path))
+        .with_source(e)
+    })?;
+
+    let tmp_path = path.with_extension("tmp");
+    fs::write(&tmp_path, yaml)?;
+    fs::rename(&tmp_path, path)?;
+
+    Ok(())
+}
+
+/// Write named entries into a new deflate-compressed zip archive
+pub fn write_zip<'a>(
+    path: &Path,
+    entries: impl IntoIterator<Item = (&'a str, &'a [u8])>,
+) -> AppResult<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let zip_error = |e: zip::result::ZipError| {
+        AppError::io("Failed to write archive")
+            .with_code(ErrorCode::ArchiveWriteFailed)
+            .with_source(e)
+    };
+    let options = zip::write::SimpleFileOptions::default()
+        .compression_method(zip::CompressionMethod::Deflated);
+    let mut zip = zip::ZipWriter::new(File::create(path)?);
+    for (name, content) in entries {
+        zip.start_file(name, options).map_err(zip_error)?;
+        zip.write_all(content)?;
+    }
+    zip.finish().map_err(zip_error)?;
+
+    Ok(())
+}
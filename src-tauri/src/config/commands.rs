@@ -1,4 +1,13 @@
-use super::{load_config, save_config, AppConfig, ConfigError, ScreenMode, Theme, WindowConfig};
+use tauri::Manager;
+
+use super::{load_config, save_config, AppConfig, ScreenMode, Theme, WindowConfig};
+use crate::auth::SessionState;
+use crate::common::{AppError, AppResult, ErrorCode};
+use crate::idle::IdleMonitor;
+use crate::logging::apply_logging_config;
+use crate::shortcuts::refresh_shortcuts;
+use crate::tray::refresh_tray;
+use crate::window::{saved_geometry, store_geometry};
 
 /// Get the full app configuration
 #[tauri::command]
@@ -7,9 +16,24 @@ pub fn get_config(app: tauri::AppHandle) -> AppConfig {
 }
 
 /// Update the app configuration
+/// Changing the security-relevant `auth`, `api` or `kiosk` sections requires a signed-in admin
 #[tauri::command]
-pub fn set_config(app: tauri::AppHandle, config: AppConfig) -> Result<(), String> {
-    save_config(&app, &config).map_err(|e| e.to_string())
+pub fn set_config(
+    app: tauri::AppHandle,
+    session: tauri::State<'_, SessionState>,
+    config: AppConfig,
+) -> AppResult<()> {
+    let current = load_config(&app);
+    if config.auth != current.auth || config.api != current.api || config.kiosk != current.kiosk {
+        session.require_admin()?;
+    }
+
+    save_config(&app, &config)?;
+    app.state::<IdleMonitor>().apply_config(&config.auth);
+    apply_logging_config(&config.logging);
+    refresh_tray(&app);
+    refresh_shortcuts(&app);
+    Ok(())
 }
 
 /// Get the app language from config
@@ -21,10 +45,12 @@ pub fn get_app_language(app: tauri::AppHandle) -> Option<String> {
 
 /// Set the app language in config
 #[tauri::command]
-pub fn set_app_language(app: tauri::AppHandle, language: &str) -> Result<(), String> {
+pub fn set_app_language(app: tauri::AppHandle, language: &str) -> AppResult<()> {
     let mut config = load_config(&app);
     config.language = language.to_string();
-    save_config(&app, &config).map_err(|e| e.to_string())
+    save_config(&app, &config)?;
+    refresh_tray(&app);
+    Ok(())
 }
 
 /// Get the app theme from config
@@ -36,21 +62,25 @@ pub fn get_app_theme(app: tauri::AppHandle) -> String {
 
 /// Set the app theme in config
 #[tauri::command]
-pub fn set_app_theme(app: tauri::AppHandle, theme: &str) -> Result<(), String> {
+pub fn set_app_theme(app: tauri::AppHandle, theme: &str) -> AppResult<()> {
     let valid_theme = Theme::from_str(theme).ok_or_else(|| {
-        ConfigError::InvalidValue(format!(
+        AppError::invalid_input(format!(
             "Invalid theme: {}. Must be one of: dark, light, system",
             theme
         ))
-        .to_string()
+        .with_code(ErrorCode::ConfigInvalidTheme)
+        .with_param("theme", theme)
     })?;
 
     let mut config = load_config(&app);
     config.theme = valid_theme;
-    save_config(&app, &config).map_err(|e| e.to_string())
+    save_config(&app, &config)?;
+    refresh_tray(&app);
+    Ok(())
 }
 
-/// Save window state (position, size, screen_mode)
+/// Save the main window state (position, size, screen_mode)
+/// Kept for older frontends; stored with the geometry Rust captures per window
 #[tauri::command]
 pub fn save_window_state(
     app: tauri::AppHandle,
@@ -59,39 +89,38 @@ pub fn save_window_state(
     x: Option<i32>,
     y: Option<i32>,
     screen_mode: String,
-) -> Result<(), String> {
+) -> AppResult<()> {
     let mode = ScreenMode::from_str(&screen_mode).ok_or_else(|| {
-        ConfigError::InvalidValue(format!(
+        AppError::invalid_input(format!(
             "Invalid screen_mode: {}. Must be one of: normal, maximized, fullscreen",
             screen_mode
         ))
-        .to_string()
+        .with_code(ErrorCode::ConfigInvalidScreenMode)
+        .with_param("mode", &screen_mode)
     })?;
 
-    let mut config = load_config(&app);
-    config.window = WindowConfig {
+    let geometry = WindowConfig {
         width,
         height,
         x,
         y,
         screen_mode: mode,
     };
-    save_config(&app, &config).map_err(|e| e.to_string())
+    store_geometry(&app, "main", geometry)
 }
 
 /// Get window state from config
 #[tauri::command]
 pub fn get_window_state(app: tauri::AppHandle) -> WindowConfig {
-    let config = load_config(&app);
-    config.window
+    saved_geometry(&app, "main").unwrap_or_else(|| load_config(&app).window)
 }
 
 /// Set auto-start setting
 #[tauri::command]
-pub fn set_auto_start(app: tauri::AppHandle, enabled: bool) -> Result<(), String> {
+pub fn set_auto_start(app: tauri::AppHandle, enabled: bool) -> AppResult<()> {
     let mut config = load_config(&app);
     config.startup.auto_start = enabled;
-    save_config(&app, &config).map_err(|e| e.to_string())
+    Ok(save_config(&app, &config)?)
 }
 
 /// Get auto-start setting
@@ -110,10 +139,10 @@ pub fn get_remember_me_default(app: tauri::AppHandle) -> bool {
 
 /// Set remember me default setting
 #[tauri::command]
-pub fn set_remember_me_default(app: tauri::AppHandle, enabled: bool) -> Result<(), String> {
+pub fn set_remember_me_default(app: tauri::AppHandle, enabled: bool) -> AppResult<()> {
     let mut config = load_config(&app);
     config.auth.remember_me_default = enabled;
-    save_config(&app, &config).map_err(|e| e.to_string())
+    Ok(save_config(&app, &config)?)
 }
 
 /// Get NFC enabled setting
@@ -125,10 +154,10 @@ pub fn get_nfc_enabled(app: tauri::AppHandle) -> bool {
 
 /// Set NFC enabled setting
 #[tauri::command]
-pub fn set_nfc_enabled(app: tauri::AppHandle, enabled: bool) -> Result<(), String> {
+pub fn set_nfc_enabled(app: tauri::AppHandle, enabled: bool) -> AppResult<()> {
     let mut config = load_config(&app);
     config.auth.nfc_enabled = enabled;
-    save_config(&app, &config).map_err(|e| e.to_string())
+    Ok(save_config(&app, &config)?)
 }
 
 /// Simple greet command for testing
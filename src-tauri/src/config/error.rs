@@ -1,39 +1,69 @@
 use std::fmt;
 
+use crate::common::{AppError, ErrorCode, ErrorKind};
+
 /// Config-related errors
 #[derive(Debug)]
 pub enum ConfigError {
     /// Failed to get app config directory
-    ConfigDirNotFound(String),
+    ConfigDirNotFound(tauri::Error),
     /// Failed to read config file
-    ReadError(String),
+    ReadError(std::io::Error),
     /// Failed to write config file
-    WriteError(String),
+    WriteError(std::io::Error),
     /// Failed to parse config
-    ParseError(String),
-    /// Invalid value provided
-    InvalidValue(String),
+    ParseError(serde_yaml::Error),
+}
+
+impl ConfigError {
+    fn summary(&self) -> &'static str {
+        match self {
+            ConfigError::ConfigDirNotFound(_) => "Failed to get app config directory",
+            ConfigError::ReadError(_) => "Failed to read config file",
+            ConfigError::WriteError(_) => "Failed to write config file",
+            ConfigError::ParseError(_) => "Failed to parse config",
+        }
+    }
 }
 
 impl fmt::Display for ConfigError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cause: &dyn fmt::Display = match self {
+            ConfigError::ConfigDirNotFound(e) => e,
+            ConfigError::ReadError(e) | ConfigError::WriteError(e) => e,
+            ConfigError::ParseError(e) => e,
+        };
+        write!(f, "{}: {}", self.summary(), cause)
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
-            ConfigError::ConfigDirNotFound(msg) => {
-                write!(f, "Failed to get app config directory: {}", msg)
-            }
-            ConfigError::ReadError(msg) => write!(f, "Failed to read config file: {}", msg),
-            ConfigError::WriteError(msg) => write!(f, "Failed to write config file: {}", msg),
-            ConfigError::ParseError(msg) => write!(f, "Failed to parse config: {}", msg),
-            ConfigError::InvalidValue(msg) => write!(f, "Invalid value: {}", msg),
+            ConfigError::ConfigDirNotFound(e) => Some(e),
+            ConfigError::ReadError(e) | ConfigError::WriteError(e) => Some(e),
+            ConfigError::ParseError(e) => Some(e),
         }
     }
 }
 
-impl std::error::Error for ConfigError {}
-
-// Convert to String for Tauri command return
-impl From<ConfigError> for String {
+// The cause is kept as the source, so it reaches the log but not the frontend
+impl From<ConfigError> for AppError {
     fn from(error: ConfigError) -> Self {
-        error.to_string()
+        let summary = error.summary();
+        match error {
+            ConfigError::ConfigDirNotFound(e) => AppError::new(ErrorKind::Config, summary)
+                .with_code(ErrorCode::ConfigDirNotFound)
+                .with_source(e),
+            ConfigError::ReadError(e) => AppError::new(ErrorKind::Io, summary)
+                .with_code(ErrorCode::ConfigReadFailed)
+                .with_source(e),
+            ConfigError::WriteError(e) => AppError::new(ErrorKind::Io, summary)
+                .with_code(ErrorCode::ConfigWriteFailed)
+                .with_source(e),
+            ConfigError::ParseError(e) => AppError::new(ErrorKind::Serialization, summary)
+                .with_code(ErrorCode::ConfigParseFailed)
+                .with_source(e),
+        }
     }
 }
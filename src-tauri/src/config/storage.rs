@@ -1,8 +1,10 @@
 use std::fs;
 use std::path::PathBuf;
+use std::sync::Mutex;
 use tauri::Manager;
 
 use super::{AppConfig, ConfigError};
+use crate::common::set_error_language;
 
 /// Default configuration YAML embedded at compile time
 const DEFAULT_CONFIG_YAML: &str = include_str!("../../default-config.yaml");
@@ -15,7 +17,7 @@ pub fn get_config_path(app: &tauri::AppHandle) -> Result<PathBuf, ConfigError> {
     let config_dir = app
         .path()
         .app_config_dir()
-        .map_err(|e| ConfigError::ConfigDirNotFound(e.to_string()))?;
+        .map_err(ConfigError::ConfigDirNotFound)?;
 
     Ok(config_dir.join(CONFIG_FILE_NAME))
 }
@@ -25,27 +27,60 @@ pub fn load_default_config() -> AppConfig {
     serde_yaml::from_str(DEFAULT_CONFIG_YAML).unwrap_or_default()
 }
 
+/// Last load failure that was logged, so a broken file is reported once
+/// rather than on every read
+static LAST_LOAD_ERROR: Mutex<Option<String>> = Mutex::new(None);
+
+fn report_load_error(error: Option<String>) {
+    let mut last = LAST_LOAD_ERROR.lock().unwrap();
+    if let Some(message) = &error {
+        if last.as_ref() != Some(message) {
+            log::warn!("{}; using default configuration", message);
+        }
+    }
+    *last = error;
+}
+
 /// Load config from YAML file
+///
+/// Falls back to the defaults when the file cannot be read. A missing file is
+/// created from the defaults; an unreadable one is logged and left in place.
 pub fn load_config(app: &tauri::AppHandle) -> AppConfig {
     let config_path = match get_config_path(app) {
         Ok(path) => path,
-        Err(_) => return load_default_config(),
+        Err(e) => {
+            report_load_error(Some(e.to_string()));
+            return load_default_config();
+        }
     };
 
     // Try to load existing config
     if config_path.exists() {
-        if let Ok(content) = fs::read_to_string(&config_path) {
-            if let Ok(config) = serde_yaml::from_str::<AppConfig>(&content) {
-                return config;
+        let loaded = fs::read_to_string(&config_path)
+            .map_err(ConfigError::ReadError)
+            .and_then(|content| {
+                serde_yaml::from_str::<AppConfig>(&content).map_err(ConfigError::ParseError)
+            });
+        return match loaded {
+            Ok(config) => {
+                report_load_error(None);
+                config
             }
-        }
+            Err(e) => {
+                report_load_error(Some(format!("{}: {}", config_path.display(), e)));
+                load_default_config()
+            }
+        };
     }
 
     // Config doesn't exist - create from default config template
     let config = load_default_config();
 
     // Save the default config to user's config directory
-    let _ = save_config(app, &config);
+    match save_config(app, &config) {
+        Ok(()) => log::info!("Created default config at {}", config_path.display()),
+        Err(e) => report_load_error(Some(e.to_string())),
+    }
 
     config
 }
@@ -56,13 +91,13 @@ pub fn save_config(app: &tauri::AppHandle, config: &AppConfig) -> Result<(), Con
 
     // Ensure config directory exists
     if let Some(parent) = config_path.parent() {
-        fs::create_dir_all(parent).map_err(|e| ConfigError::WriteError(e.to_string()))?;
+        fs::create_dir_all(parent).map_err(ConfigError::WriteError)?;
     }
 
-    let yaml =
-        serde_yaml::to_string(config).map_err(|e| ConfigError::ParseError(e.to_string()))?;
+    let yaml = serde_yaml::to_string(config).map_err(ConfigError::ParseError)?;
 
-    fs::write(&config_path, yaml).map_err(|e| ConfigError::WriteError(e.to_string()))?;
+    fs::write(&config_path, yaml).map_err(ConfigError::WriteError)?;
+    set_error_language(&config.language);
 
     Ok(())
 }
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Screen mode for window display
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
@@ -74,16 +75,184 @@ impl Default for WindowConfig {
 }
 
 /// Authentication configuration
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AuthConfig {
     pub remember_me_default: bool,
     pub nfc_enabled: bool,
+    /// Maximum number of saved accounts offered on the login window
+    #[serde(default = "default_max_saved_accounts")]
+    pub max_saved_accounts: usize,
+    /// Minutes without input before the session is locked (0 disables)
+    #[serde(default = "default_idle_lock_minutes")]
+    pub idle_lock_minutes: u32,
+    /// Minutes without input before the user is logged out (0 disables)
+    #[serde(default = "default_idle_logout_minutes")]
+    pub idle_logout_minutes: u32,
+    /// Require TOTP for Admin and SuperAdmin sign-ins
+    #[serde(default = "default_mfa_required_for_admins")]
+    pub mfa_required_for_admins: bool,
+    /// Rules for new passwords
+    #[serde(default)]
+    pub password_policy: PasswordPolicyConfig,
+}
+
+fn default_max_saved_accounts() -> usize {
+    5
+}
+
+fn default_idle_lock_minutes() -> u32 {
+    10
+}
+
+fn default_idle_logout_minutes() -> u32 {
+    60
+}
+
+fn default_mfa_required_for_admins() -> bool {
+    true
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        Self {
+            remember_me_default: false,
+            nfc_enabled: false,
+            max_saved_accounts: default_max_saved_accounts(),
+            idle_lock_minutes: default_idle_lock_minutes(),
+            idle_logout_minutes: default_idle_logout_minutes(),
+            mfa_required_for_admins: default_mfa_required_for_admins(),
+            password_policy: PasswordPolicyConfig::default(),
+        }
+    }
+}
+
+/// Password strength policy
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PasswordPolicyConfig {
+    pub min_length: usize,
+    pub max_length: usize,
+    /// Distinct character classes required (lowercase, uppercase, digit, symbol)
+    pub min_character_classes: usize,
+    /// File of breached passwords, one plain password or SHA-1 hash per line
+    /// (relative paths resolve against the app data dir, empty disables the check)
+    pub breached_list_path: String,
+}
+
+impl Default for PasswordPolicyConfig {
+    fn default() -> Self {
+        Self {
+            min_length: 8,
+            max_length: 128,
+            min_character_classes: 3,
+            breached_list_path: String::new(),
+        }
+    }
 }
 
 /// Startup configuration
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StartupConfig {
     pub auto_start: bool,
+    /// Hide the main window to the tray instead of quitting when it is closed
+    #[serde(default = "default_close_to_tray")]
+    pub close_to_tray: bool,
+}
+
+fn default_close_to_tray() -> bool {
+    true
+}
+
+impl Default for StartupConfig {
+    fn default() -> Self {
+        Self {
+            auto_start: false,
+            close_to_tray: default_close_to_tray(),
+        }
+    }
+}
+
+/// School API configuration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ApiConfig {
+    /// Base URL of the school API, e.g. "https://school.example.edu/api" (empty = offline only)
+    pub base_url: String,
+    /// Request timeout in seconds
+    pub timeout_secs: u64,
+}
+
+impl Default for ApiConfig {
+    fn default() -> Self {
+        Self {
+            base_url: String::new(),
+            timeout_secs: 30,
+        }
+    }
+}
+
+/// Kiosk mode for shared student lab machines
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KioskConfig {
+    pub enabled: bool,
+    /// Key sequence that opens the admin exit prompt
+    pub escape_sequence: String,
+    /// Keep the main window out of the taskbar where the platform allows it
+    pub hide_taskbar: bool,
+}
+
+impl Default for KioskConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            escape_sequence: "Ctrl+Alt+Shift+K".to_string(),
+            hide_taskbar: true,
+        }
+    }
+}
+
+/// Global keyboard shortcuts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShortcutsConfig {
+    pub enabled: bool,
+    /// Accelerators replacing the defaults, by action id; empty disables the action
+    pub overrides: BTreeMap<String, String>,
+}
+
+impl Default for ShortcutsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            overrides: BTreeMap::new(),
+        }
+    }
+}
+
+/// Application logging
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LoggingConfig {
+    /// Default level: off, error, warn, info, debug or trace
+    pub level: String,
+    /// Levels by module path, e.g. "tauri_app_lib::auth" or "frontend"; the longest match wins
+    pub modules: BTreeMap<String, String>,
+    /// Size at which the log file is rotated
+    pub max_file_size_kb: u64,
+    /// Rotated files kept besides the current one
+    pub max_files: u32,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            level: "info".to_string(),
+            modules: BTreeMap::new(),
+            max_file_size_kb: 1024,
+            max_files: 5,
+        }
+    }
 }
 
 /// Main application configuration
@@ -100,6 +269,18 @@ pub struct AppConfig {
     pub startup: StartupConfig,
     /// Authentication settings
     pub auth: AuthConfig,
+    /// School API settings
+    #[serde(default)]
+    pub api: ApiConfig,
+    /// Kiosk mode settings
+    #[serde(default)]
+    pub kiosk: KioskConfig,
+    /// Global keyboard shortcut settings
+    #[serde(default)]
+    pub shortcuts: ShortcutsConfig,
+    /// Logging settings
+    #[serde(default)]
+    pub logging: LoggingConfig,
 }
 
 impl Default for AppConfig {
@@ -110,6 +291,10 @@ impl Default for AppConfig {
             window: WindowConfig::default(),
             startup: StartupConfig::default(),
             auth: AuthConfig::default(),
+            api: ApiConfig::default(),
+            kiosk: KioskConfig::default(),
+            shortcuts: ShortcutsConfig::default(),
+            logging: LoggingConfig::default(),
         }
     }
 }
@@ -0,0 +1,11 @@
+//! Deep Link Commands
+//! Tauri commands for links that waited for sign-in
+
+use super::router::take_main_deep_links;
+
+/// Take routes of links that arrived before the dashboard was open
+/// Called by the main window once it has loaded
+#[tauri::command]
+pub fn take_pending_deep_links(app: tauri::AppHandle) -> Vec<String> {
+    take_main_deep_links(&app)
+}
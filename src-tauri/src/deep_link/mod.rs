@@ -0,0 +1,13 @@
+//! Deep Link Module
+//! Handles `smartschool://` links from emails and the school portal
+
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+mod commands;
+mod router;
+mod types;
+
+pub use commands::*;
+pub use router::*;
+pub use types::*;
@@ -0,0 +1,150 @@
+//! Deep Link Router
+//! Routes links to their window, queueing them until someone is signed in
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use tauri::{Emitter, Manager};
+
+use super::types::DeepLink;
+use crate::auth::SessionState;
+use crate::idle::{IdleMonitor, LockState};
+use crate::window::{
+    focus_active_window, open_window, transition_to, WindowKind, WindowParams, WindowStage,
+};
+
+/// Event sent to the main window with the route of a link
+pub const DEEP_LINK_NAVIGATE_EVENT: &str = "deep-link://navigate";
+
+/// Event emitted when a link is rejected
+pub const DEEP_LINK_REJECTED_EVENT: &str = "deep-link://rejected";
+
+/// Most links kept while waiting for sign-in; older ones are dropped
+const MAX_PENDING: usize = 10;
+
+/// Payload of `deep-link://rejected`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RejectedDeepLink {
+    pub url: String,
+    pub reason: String,
+}
+
+/// Managed state holding links that arrived before the session was ready
+#[derive(Default)]
+pub struct DeepLinkQueue {
+    pending: Mutex<VecDeque<DeepLink>>,
+}
+
+impl DeepLinkQueue {
+    fn push(&self, link: DeepLink) {
+        let mut pending = self.pending.lock().unwrap();
+        if pending.len() == MAX_PENDING {
+            pending.pop_front();
+        }
+        pending.push_back(link);
+    }
+
+    fn drain(&self) -> Vec<DeepLink> {
+        self.pending.lock().unwrap().drain(..).collect()
+    }
+}
+
+/// Someone is signed in and the lock screen is not up
+fn session_ready(app: &tauri::AppHandle) -> bool {
+    app.state::<SessionState>().is_active()
+        && app.state::<IdleMonitor>().state() != LockState::Locked
+}
+
+/// Handle links passed on the command line, by the OS or by a second launch
+pub fn handle_deep_links<I, S>(app: &tauri::AppHandle, links: I)
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut queued = false;
+    for url in links {
+        let url = url.as_ref();
+        match DeepLink::parse(url) {
+            Ok(link) if !link.requires_session() || session_ready(app) => {
+                dispatch_deep_link(app, link)
+            }
+            Ok(link) => {
+                app.state::<DeepLinkQueue>().push(link);
+                queued = true;
+            }
+            Err(reason) => {
+                log::warn!("Rejected deep link: {}", reason);
+                let _ = app.emit(
+                    DEEP_LINK_REJECTED_EVENT,
+                    RejectedDeepLink {
+                        url: url.to_string(),
+                        reason,
+                    },
+                );
+            }
+        }
+    }
+
+    // Bring up the login or lock screen the link is waiting on
+    if queued {
+        focus_active_window(app);
+    }
+}
+
+/// Open a link in its window
+///
+/// Links for a main window that does not exist yet stay queued; the
+/// dashboard takes them with `take_pending_deep_links` once it has loaded.
+pub fn dispatch_deep_link(app: &tauri::AppHandle, link: DeepLink) {
+    match link.window() {
+        WindowKind::Main => match app.get_webview_window("main") {
+            Some(_) => {
+                let _ = transition_to(app, WindowStage::Main);
+                let _ = app.emit_to("main", DEEP_LINK_NAVIGATE_EVENT, link.route());
+            }
+            None => {
+                app.state::<DeepLinkQueue>().push(link);
+                let _ = transition_to(app, WindowStage::Main);
+            }
+        },
+        kind => {
+            let app = app.clone();
+            let params = WindowParams {
+                route: Some(link.route()),
+                ..WindowParams::default()
+            };
+            tauri::async_runtime::spawn(async move {
+                let _ = open_window(app, kind, Some(params)).await;
+            });
+        }
+    }
+}
+
+/// Route queued links once the session is ready, e.g. after unlocking
+pub fn flush_deep_links(app: &tauri::AppHandle) {
+    if !session_ready(app) {
+        return;
+    }
+    for link in app.state::<DeepLinkQueue>().drain() {
+        dispatch_deep_link(app, link);
+    }
+}
+
+/// Take the queued links for a freshly loaded main window
+///
+/// Returns the routes to show in main, in arrival order; links for other
+/// windows are opened directly.
+pub fn take_main_deep_links(app: &tauri::AppHandle) -> Vec<String> {
+    if !session_ready(app) {
+        return Vec::new();
+    }
+
+    let mut routes = Vec::new();
+    for link in app.state::<DeepLinkQueue>().drain() {
+        match link.window() {
+            WindowKind::Main => routes.push(link.route()),
+            _ => dispatch_deep_link(app, link),
+        }
+    }
+    routes
+}
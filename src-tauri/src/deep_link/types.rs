@@ -0,0 +1,167 @@
+//! Deep Link Types
+//! Parsing and validation of `smartschool://` links
+
+use serde::{Deserialize, Serialize};
+use tauri::Url;
+
+use crate::window::WindowKind;
+
+/// URL scheme of links that open the app
+pub const DEEP_LINK_SCHEME: &str = "smartschool";
+
+/// Longest link accepted
+const MAX_LINK_LENGTH: usize = 2048;
+
+/// Longest id accepted in a link
+const MAX_ID_LENGTH: usize = 64;
+
+/// A validated link into the app
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DeepLink {
+    /// `smartschool://dashboard`
+    Dashboard,
+    /// `smartschool://class/<id>`
+    Class { class_id: String },
+    /// `smartschool://class/<id>/join`
+    JoinClass { class_id: String },
+    /// `smartschool://assignment/<id>`
+    Assignment { assignment_id: String },
+    /// `smartschool://settings`
+    Settings,
+    /// `smartschool://help` or `smartschool://help/<topic>`
+    Help { topic: Option<String> },
+}
+
+/// Whether an argument looks like a link for this app
+pub fn is_deep_link(arg: &str) -> bool {
+    arg.get(..DEEP_LINK_SCHEME.len() + 3)
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case(&format!("{}://", DEEP_LINK_SCHEME)))
+}
+
+/// Ids are limited to characters that cannot change the route they are placed in
+fn valid_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= MAX_ID_LENGTH
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+impl DeepLink {
+    /// Parse and validate a link, returning why it was rejected otherwise
+    pub fn parse(link: &str) -> Result<Self, String> {
+        if link.len() > MAX_LINK_LENGTH {
+            return Err("Link is too long".to_string());
+        }
+        // Dot segments would be resolved away by the URL parser
+        if link.split('/').any(|part| part == "." || part == "..") {
+            return Err("Link contains a relative path".to_string());
+        }
+        let url = Url::parse(link).map_err(|e| format!("Malformed link: {}", e))?;
+        if url.scheme() != DEEP_LINK_SCHEME {
+            return Err(format!("Unsupported scheme: {}", url.scheme()));
+        }
+
+        let host = url.host_str().unwrap_or_default().to_ascii_lowercase();
+        let segments: Vec<&str> = url
+            .path_segments()
+            .map(|segments| segments.filter(|s| !s.is_empty()).collect())
+            .unwrap_or_default();
+        if let Some(id) = segments.first().filter(|id| !valid_id(id)) {
+            return Err(format!("Invalid id in link: {}", id));
+        }
+
+        let link = match (host.as_str(), segments.as_slice()) {
+            ("dashboard", []) => DeepLink::Dashboard,
+            ("class", [id]) => DeepLink::Class {
+                class_id: id.to_string(),
+            },
+            ("class", [id, "join"]) => DeepLink::JoinClass {
+                class_id: id.to_string(),
+            },
+            ("assignment", [id]) => DeepLink::Assignment {
+                assignment_id: id.to_string(),
+            },
+            ("settings", []) => DeepLink::Settings,
+            ("help", []) => DeepLink::Help { topic: None },
+            ("help", [topic]) => DeepLink::Help {
+                topic: Some(topic.to_string()),
+            },
+            _ => return Err(format!("Unknown link: {}", link)),
+        };
+        Ok(link)
+    }
+
+    /// Route the link opens
+    pub fn route(&self) -> String {
+        match self {
+            DeepLink::Dashboard => "/dashboard".to_string(),
+            DeepLink::Class { class_id } => format!("/classes/{}", class_id),
+            DeepLink::JoinClass { class_id } => format!("/classes/{}/join", class_id),
+            DeepLink::Assignment { assignment_id } => format!("/assignments/{}", assignment_id),
+            DeepLink::Settings => "/settings".to_string(),
+            DeepLink::Help { topic: None } => "/help".to_string(),
+            DeepLink::Help { topic: Some(topic) } => format!("/help/{}", topic),
+        }
+    }
+
+    /// Window the route is shown in
+    pub fn window(&self) -> WindowKind {
+        match self {
+            DeepLink::Settings => WindowKind::Settings,
+            DeepLink::Help { .. } => WindowKind::Help,
+            _ => WindowKind::Main,
+        }
+    }
+
+    /// Help pages are public; everything else waits for a signed-in, unlocked session
+    pub fn requires_session(&self) -> bool {
+        !matches!(self, DeepLink::Help { .. })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_supported_links() {
+        let link = DeepLink::parse("smartschool://class/123/join").unwrap();
+        assert_eq!(
+            link,
+            DeepLink::JoinClass {
+                class_id: "123".to_string()
+            }
+        );
+        assert_eq!(link.route(), "/classes/123/join");
+        assert_eq!(link.window(), WindowKind::Main);
+
+        let help = DeepLink::parse("smartschool://help/nfc-cards/").unwrap();
+        assert_eq!(help.route(), "/help/nfc-cards");
+        assert!(!help.requires_session());
+
+        assert_eq!(
+            DeepLink::parse("smartschool://settings").unwrap().window(),
+            WindowKind::Settings
+        );
+    }
+
+    #[test]
+    fn test_rejects_invalid_links() {
+        assert!(DeepLink::parse("https://class/123").is_err());
+        assert!(DeepLink::parse("smartschool://class/../admin").is_err());
+        assert!(DeepLink::parse("smartschool://class/%2E%2E").is_err());
+        assert!(DeepLink::parse("smartschool://class/1/2/3").is_err());
+        assert!(DeepLink::parse("smartschool://unknown").is_err());
+        let long = format!("smartschool://class/{}", "a".repeat(MAX_LINK_LENGTH));
+        assert!(DeepLink::parse(&long).is_err());
+    }
+
+    #[test]
+    fn test_recognizes_link_arguments() {
+        assert!(is_deep_link("smartschool://dashboard"));
+        assert!(is_deep_link("SmartSchool://dashboard"));
+        assert!(!is_deep_link("--autostarted"));
+    }
+}
@@ -0,0 +1,63 @@
+//! Device Commands
+//! Tauri commands for device identity and registration management
+
+use super::identity::DeviceState;
+use super::types::{DeviceContext, DeviceRegistration};
+use crate::api::ApiClient;
+use crate::auth::SessionState;
+use crate::common::{AppError, AppResult, ErrorCode};
+
+/// Get this device's id and info for login/refresh requests
+#[tauri::command]
+pub fn get_device_context(
+    app: tauri::AppHandle,
+    device: tauri::State<'_, DeviceState>,
+) -> AppResult<DeviceContext> {
+    device.context(&app)
+}
+
+/// Sign a server-issued challenge nonce with the device key
+/// Returns the hex-encoded Ed25519 signature
+#[tauri::command]
+pub fn sign_device_challenge(
+    app: tauri::AppHandle,
+    device: tauri::State<'_, DeviceState>,
+    challenge: String,
+) -> AppResult<String> {
+    device.identity(&app)?.sign_challenge(&challenge)
+}
+
+/// List devices registered with the school API (admin only)
+#[tauri::command]
+pub async fn list_device_registrations(
+    app: tauri::AppHandle,
+    session: tauri::State<'_, SessionState>,
+) -> AppResult<Vec<DeviceRegistration>> {
+    session.require_admin()?;
+    let client = ApiClient::from_app(&app)?.with_token(session.access_token());
+    client.get("devices").await
+}
+
+/// Revoke a device registration (admin only)
+/// Sessions bound to the device can no longer refresh their tokens
+#[tauri::command]
+pub async fn revoke_device_registration(
+    app: tauri::AppHandle,
+    session: tauri::State<'_, SessionState>,
+    device_id: String,
+) -> AppResult<()> {
+    session.require_admin()?;
+    let valid_id = !device_id.is_empty()
+        && device_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid_id {
+        return Err(
+            AppError::invalid_input("Invalid device id").with_code(ErrorCode::DeviceIdInvalid)
+        );
+    }
+
+    let client = ApiClient::from_app(&app)?.with_token(session.access_token());
+    client.delete(&format!("devices/{}", device_id)).await?;
+    Ok(())
+}
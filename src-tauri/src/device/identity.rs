@@ -0,0 +1,220 @@
+//! Device Identity
+//! Stable per-install device id and Ed25519 keypair
+
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signer, SigningKey};
+use rand::rngs::OsRng;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+use super::types::{DeviceContext, DeviceInfo, DeviceType};
+use crate::common::{
+    app_data_file, keychain_get, keychain_set, read_yaml, write_yaml, AppError, AppResult,
+    ErrorCode,
+};
+
+/// Device identity file name
+const DEVICE_FILE_NAME: &str = "device.yaml";
+
+/// Keychain entry holding the hex-encoded Ed25519 secret key
+const DEVICE_KEY_ENTRY: &str = "device-key";
+
+/// Prefix of every signed message, so the device key signs nothing but challenges
+const CHALLENGE_CONTEXT: &[u8] = b"smart-school-device-challenge-v1:";
+
+/// Server challenges are a 32-byte nonce, hex-encoded
+const CHALLENGE_HEX_LEN: usize = 64;
+
+/// Identity generated on first run and kept for the lifetime of the install
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeviceIdentity {
+    pub device_id: String,
+    /// Hex-encoded Ed25519 public key
+    pub public_key: String,
+    /// Hex-encoded Ed25519 secret key, kept in the OS keychain
+    ///
+    /// Still read from files written before the key moved to the keychain,
+    /// but never written back.
+    #[serde(default, skip_serializing)]
+    pub secret_key: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl DeviceIdentity {
+    pub fn generate() -> Self {
+        let mut identity = Self {
+            device_id: uuid::Uuid::new_v4().to_string(),
+            public_key: String::new(),
+            secret_key: String::new(),
+            created_at: Utc::now(),
+        };
+        identity.rotate_key();
+        identity
+    }
+
+    /// Replace the keypair, keeping the device id
+    fn rotate_key(&mut self) {
+        let signing_key = SigningKey::generate(&mut OsRng);
+        self.public_key = hex::encode(signing_key.verifying_key().to_bytes());
+        self.secret_key = hex::encode(signing_key.to_bytes());
+    }
+
+    /// Sign a server challenge nonce, returning the hex-encoded signature
+    ///
+    /// Only well-formed nonces are accepted and the signed message is
+    /// `CHALLENGE_CONTEXT || challenge`, so the key cannot be used to sign
+    /// arbitrary data.
+    pub fn sign_challenge(&self, challenge: &str) -> AppResult<String> {
+        let valid = challenge.len() == CHALLENGE_HEX_LEN
+            && challenge.bytes().all(|b| b.is_ascii_hexdigit());
+        if !valid {
+            return Err(AppError::invalid_input("Invalid device challenge")
+                .with_code(ErrorCode::DeviceChallengeInvalid));
+        }
+
+        self.sign(&challenge_message(challenge))
+    }
+
+    fn sign(&self, message: &[u8]) -> AppResult<String> {
+        let bytes: [u8; 32] = hex::decode(&self.secret_key)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| {
+                AppError::config("Device key is corrupt").with_code(ErrorCode::DeviceKeyCorrupt)
+            })?;
+
+        let signature = SigningKey::from_bytes(&bytes).sign(message);
+        Ok(hex::encode(signature.to_bytes()))
+    }
+}
+
+/// Message actually signed for a challenge
+fn challenge_message(challenge: &str) -> Vec<u8> {
+    [CHALLENGE_CONTEXT, challenge.to_ascii_lowercase().as_bytes()].concat()
+}
+
+/// Managed state caching the device identity once loaded
+#[derive(Default)]
+pub struct DeviceState {
+    identity: Mutex<Option<DeviceIdentity>>,
+}
+
+impl DeviceState {
+    /// Load the identity from disk, creating it on first use
+    pub fn identity(&self, app: &tauri::AppHandle) -> AppResult<DeviceIdentity> {
+        let mut guard = self.identity.lock().unwrap();
+        if let Some(identity) = guard.as_ref() {
+            return Ok(identity.clone());
+        }
+
+        let identity = load_or_create_identity(app)?;
+        *guard = Some(identity.clone());
+        Ok(identity)
+    }
+
+    /// Device id and info to attach to API requests
+    pub fn context(&self, app: &tauri::AppHandle) -> AppResult<DeviceContext> {
+        let identity = self.identity(app)?;
+        Ok(DeviceContext {
+            device_info: device_info(app, &identity),
+            device_id: identity.device_id,
+        })
+    }
+}
+
+/// Load the device identity, generating and saving one if none exists
+///
+/// The secret key lives in the OS keychain; a key found in an older
+/// `device.yaml` is moved there. If the keychain lost the key, a new keypair
+/// is generated for the same device id.
+pub fn load_or_create_identity(app: &tauri::AppHandle) -> AppResult<DeviceIdentity> {
+    let path = app_data_file(app, DEVICE_FILE_NAME)?;
+    let Some(mut identity) = read_yaml::<DeviceIdentity>(&path)? else {
+        let identity = DeviceIdentity::generate();
+        keychain_set(app, DEVICE_KEY_ENTRY, &identity.secret_key)?;
+        write_yaml(&path, &identity)?;
+        return Ok(identity);
+    };
+
+    if !identity.secret_key.is_empty() {
+        keychain_set(app, DEVICE_KEY_ENTRY, &identity.secret_key)?;
+        write_yaml(&path, &identity)?;
+        return Ok(identity);
+    }
+
+    match keychain_get(app, DEVICE_KEY_ENTRY)? {
+        Some(secret_key) => identity.secret_key = secret_key,
+        None => {
+            log::warn!("Device key missing from the OS keychain, generating a new keypair");
+            identity.rotate_key();
+            keychain_set(app, DEVICE_KEY_ENTRY, &identity.secret_key)?;
+            write_yaml(&path, &identity)?;
+        }
+    }
+    Ok(identity)
+}
+
+/// Gather information about this device
+pub fn device_info(app: &tauri::AppHandle, identity: &DeviceIdentity) -> DeviceInfo {
+    DeviceInfo {
+        device_type: DeviceType::Desktop,
+        os: Some(format!(
+            "{} {}",
+            std::env::consts::OS,
+            std::env::consts::ARCH
+        )),
+        browser: None,
+        ip_address: None,
+        user_agent: None,
+        hostname: hostname::get()
+            .ok()
+            .map(|name| name.to_string_lossy().into_owned()),
+        app_version: Some(app.package_info().version.to_string()),
+        public_key: Some(identity.public_key.clone()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+
+    #[test]
+    fn test_signature_verifies_with_public_key() {
+        let identity = DeviceIdentity::generate();
+        let challenge = "ab".repeat(32);
+        let signature = identity.sign_challenge(&challenge).unwrap();
+
+        let public: [u8; 32] = hex::decode(&identity.public_key)
+            .unwrap()
+            .try_into()
+            .unwrap();
+        let signature: [u8; 64] = hex::decode(signature).unwrap().try_into().unwrap();
+        let key = VerifyingKey::from_bytes(&public).unwrap();
+
+        let signature = Signature::from_bytes(&signature);
+
+        assert!(key
+            .verify(&challenge_message(&challenge), &signature)
+            .is_ok());
+        // The raw challenge alone is not what was signed
+        assert!(key.verify(challenge.as_bytes(), &signature).is_err());
+    }
+
+    #[test]
+    fn test_rejects_arbitrary_messages() {
+        let identity = DeviceIdentity::generate();
+        assert!(identity.sign_challenge("transfer 100").is_err());
+        assert!(identity.sign_challenge(&"ab".repeat(31)).is_err());
+        assert!(identity.sign_challenge(&"zz".repeat(32)).is_err());
+    }
+
+    #[test]
+    fn test_secret_key_is_not_written_to_yaml() {
+        let identity = DeviceIdentity::generate();
+        let yaml = serde_yaml::to_string(&identity).unwrap();
+        assert!(yaml.contains(&identity.public_key));
+        assert!(!yaml.contains(&identity.secret_key));
+        assert!(!yaml.contains("secret_key"));
+    }
+}
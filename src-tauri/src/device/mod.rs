@@ -0,0 +1,13 @@
+//! Device Module
+//! Per-install device identity, device info and device registration management
+
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+mod commands;
+mod identity;
+mod types;
+
+pub use commands::*;
+pub use identity::*;
+pub use types::*;
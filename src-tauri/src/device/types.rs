@@ -0,0 +1,59 @@
+//! Device Types
+//! Device identity and registration types shared with the frontend and school API
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Device category reported to the school API
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum DeviceType {
+    Web,
+    Mobile,
+    Tablet,
+    Desktop,
+}
+
+/// Device information sent with login and refresh requests
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeviceInfo {
+    pub device_type: DeviceType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub os: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub browser: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ip_address: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hostname: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub app_version: Option<String>,
+    /// Hex-encoded Ed25519 public key used to verify device challenges
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_key: Option<String>,
+}
+
+/// This install's identity as exposed to the frontend (never includes the secret key)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeviceContext {
+    pub device_id: String,
+    pub device_info: DeviceInfo,
+}
+
+/// A device registered for the school, as returned by the API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeviceRegistration {
+    pub device_id: String,
+    #[serde(default)]
+    pub user_id: Option<String>,
+    #[serde(default)]
+    pub device_info: Option<DeviceInfo>,
+    #[serde(default)]
+    pub registered_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub last_seen_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub revoked: bool,
+}
@@ -0,0 +1,67 @@
+//! Diagnostics Commands
+//! One-file export for IT support
+
+use std::fs;
+
+use super::redact::{redact_log, redact_text, redact_yaml};
+use super::report::collect_report;
+use crate::common::{write_zip, AppError, AppResult, ErrorCode};
+use crate::config::{get_config_path, ConfigError};
+use crate::logging::{bundle_path, flush_logs, log_dir, recent_log_files, DEFAULT_BUNDLE_DAYS};
+
+/// `config.yaml` with secrets removed; a file that does not parse is redacted as text
+fn redacted_config(app: &tauri::AppHandle) -> String {
+    let content = match get_config_path(app)
+        .and_then(|path| fs::read_to_string(path).map_err(ConfigError::ReadError))
+    {
+        Ok(content) => content,
+        Err(e) => return format!("# {}\n", e),
+    };
+
+    match serde_yaml::from_str::<serde_yaml::Value>(&content) {
+        Ok(mut value) => {
+            redact_yaml(&mut value);
+            serde_yaml::to_string(&value).unwrap_or_default()
+        }
+        Err(e) => format!(
+            "# config.yaml does not parse: {}\n{}",
+            e,
+            redact_text(&content)
+        ),
+    }
+}
+
+/// Zip a report, the redacted config and recent redacted logs, and return the archive path
+#[tauri::command]
+pub fn export_diagnostics(app: tauri::AppHandle) -> AppResult<String> {
+    let dir = log_dir().ok_or_else(|| {
+        AppError::not_found("Logging to file is not available")
+            .with_code(ErrorCode::LogDirUnavailable)
+    })?;
+    flush_logs();
+
+    let report = serde_json::to_string_pretty(&collect_report(&app))?;
+    let config = redacted_config(&app);
+    let logs = recent_log_files(&dir, DEFAULT_BUNDLE_DAYS)
+        .into_iter()
+        .map(|path| {
+            let name = format!("logs/{}", path.file_name().unwrap().to_string_lossy());
+            fs::read_to_string(&path).map(|content| (name, redact_log(&content)))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let bundle = bundle_path(&dir, "diagnostics");
+    let entries = [
+        ("report.json", report.as_bytes()),
+        ("config.yaml", config.as_bytes()),
+    ]
+    .into_iter()
+    .chain(
+        logs.iter()
+            .map(|(name, content)| (name.as_str(), content.as_bytes())),
+    );
+    write_zip(&bundle, entries)?;
+
+    log::info!("Exported diagnostics to {}", bundle.display());
+    Ok(bundle.to_string_lossy().into_owned())
+}
@@ -0,0 +1,13 @@
+//! Diagnostics Module
+//! Support bundles for IT: redacted config, recent logs and a snapshot of the app's state
+
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+mod commands;
+mod redact;
+mod report;
+
+pub use commands::*;
+pub use redact::*;
+pub use report::*;
@@ -0,0 +1,107 @@
+//! Diagnostics Report
+//! Snapshot of the app, the machine and recent problems, as written to `report.json`
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tauri::Manager;
+
+use super::redact::redact_text;
+use crate::auth::SessionState;
+use crate::idle::{IdleMonitor, LockState};
+use crate::logging::{recent_problems, LogEntry};
+use crate::window::{
+    kiosk_active, list_windows, load_window_states, monitor_fingerprint, monitors, MonitorInfo,
+    WindowInfo, WindowStateStore,
+};
+
+/// Build of the app
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppInfo {
+    pub name: String,
+    pub version: String,
+    pub tauri_version: String,
+    pub debug_build: bool,
+}
+
+/// Operating system of the machine
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OsInfo {
+    pub os: String,
+    pub family: String,
+    pub arch: String,
+    /// Distribution or release name where it can be read without extra tools
+    pub version: Option<String>,
+    pub hostname: Option<String>,
+}
+
+/// Contents of `report.json` in a diagnostics bundle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiagnosticsReport {
+    pub generated_at: DateTime<Utc>,
+    pub app: AppInfo,
+    pub os: OsInfo,
+    pub monitors: Vec<MonitorInfo>,
+    /// Fingerprint saved window geometry is keyed by
+    pub monitor_layout: String,
+    pub windows: Vec<WindowInfo>,
+    pub saved_window_states: WindowStateStore,
+    pub kiosk_active: bool,
+    pub signed_in: bool,
+    pub locked: bool,
+    /// Recent warnings and errors from the log, oldest first
+    pub last_errors: Vec<LogEntry>,
+}
+
+/// `PRETTY_NAME` from os-release on Linux
+fn os_version() -> Option<String> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+    let release = std::fs::read_to_string("/etc/os-release").ok()?;
+    release
+        .lines()
+        .find_map(|line| line.strip_prefix("PRETTY_NAME="))
+        .map(|name| name.trim_matches('"').to_string())
+}
+
+fn os_info() -> OsInfo {
+    OsInfo {
+        os: std::env::consts::OS.to_string(),
+        family: std::env::consts::FAMILY.to_string(),
+        arch: std::env::consts::ARCH.to_string(),
+        version: os_version(),
+        hostname: hostname::get()
+            .ok()
+            .map(|name| name.to_string_lossy().into_owned()),
+    }
+}
+
+/// Gather the report, with secrets already removed from log messages
+pub fn collect_report(app: &tauri::AppHandle) -> DiagnosticsReport {
+    let package = app.package_info();
+
+    DiagnosticsReport {
+        generated_at: Utc::now(),
+        app: AppInfo {
+            name: package.name.clone(),
+            version: package.version.to_string(),
+            tauri_version: tauri::VERSION.to_string(),
+            debug_build: cfg!(debug_assertions),
+        },
+        os: os_info(),
+        monitors: monitors(app),
+        monitor_layout: monitor_fingerprint(app),
+        windows: list_windows(app.clone()),
+        saved_window_states: load_window_states(app).unwrap_or_default(),
+        kiosk_active: kiosk_active(app),
+        signed_in: app.state::<SessionState>().is_active(),
+        locked: app.state::<IdleMonitor>().state() == LockState::Locked,
+        last_errors: recent_problems()
+            .into_iter()
+            .map(|entry| LogEntry {
+                message: redact_text(&entry.message),
+                ..entry
+            })
+            .collect(),
+    }
+}
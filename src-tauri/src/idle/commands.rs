@@ -0,0 +1,76 @@
+//! Idle Commands
+//! Tauri commands for activity reporting and the lock screen
+
+use tauri::Emitter;
+
+use super::monitor::{lock_session as lock, IdleMonitor, UNLOCKED_EVENT};
+use super::types::IdleStatus;
+use crate::audit::{record_audit_event, AuditEvent, AuditEventKind};
+use crate::auth::{save_stored_session, LoginThrottleState, SessionState, UnlockMethod};
+use crate::common::AppResult;
+use crate::deep_link::flush_deep_links;
+use crate::tray::refresh_tray;
+
+/// Report user input from a window (throttled by the frontend)
+#[tauri::command]
+pub fn report_activity(monitor: tauri::State<'_, IdleMonitor>) {
+    monitor.record_activity();
+}
+
+/// Lock the session immediately
+#[tauri::command]
+pub fn lock_session(app: tauri::AppHandle) {
+    lock(&app);
+}
+
+/// Unlock the session with the password, card or PIN of the signed-in user
+/// Failures of every method share the login throttle, so the lock screen backs off and locks out
+#[tauri::command]
+pub fn unlock_session(
+    app: tauri::AppHandle,
+    monitor: tauri::State<'_, IdleMonitor>,
+    session: tauri::State<'_, SessionState>,
+    throttle: tauri::State<'_, LoginThrottleState>,
+    method: UnlockMethod,
+    secret: String,
+) -> AppResult<()> {
+    let user = session.require_user()?;
+    let throttle_key = format!("unlock:{}", user.user_id);
+    throttle.check(&app, &throttle_key)?;
+
+    let result = session.verify_unlock(method, &secret);
+    throttle.record(&app, &throttle_key, result.is_ok())?;
+
+    // PIN failure counts are persisted either way; a failed save must not
+    // turn a correct unlock into an error
+    if method == UnlockMethod::Pin {
+        if let Err(e) = save_stored_session(&app, &session) {
+            log::warn!("Failed to persist session after PIN unlock attempt: {}", e);
+        }
+    }
+
+    let kind = if result.is_ok() {
+        AuditEventKind::Unlock
+    } else {
+        AuditEventKind::UnlockFailed
+    };
+    record_audit_event(
+        &app,
+        AuditEvent::new(kind)
+            .with_user(&user)
+            .with_details(method.as_str()),
+    );
+    result?;
+
+    monitor.unlock();
+    let _ = app.emit(UNLOCKED_EVENT, ());
+    refresh_tray(&app);
+    flush_deep_links(&app);
+    Ok(())
+}
+
+/// Get current idle time, lock state and configured timeouts
+#[tauri::command]
+pub fn get_idle_status(monitor: tauri::State<'_, IdleMonitor>) -> IdleStatus {
+    monitor.status()
+}
@@ -0,0 +1,13 @@
+//! Idle Module
+//! Idle tracking, lock screen and session timeout for shared classroom PCs
+
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+mod commands;
+mod monitor;
+mod types;
+
+pub use commands::*;
+pub use monitor::{start_idle_monitor, IdleMonitor, LOCKED_EVENT, TIMEOUT_EVENT, UNLOCKED_EVENT};
+pub use types::*;
@@ -0,0 +1,219 @@
+//! Idle Monitor
+//! Tracks user activity and locks or ends the session when the PC is left unattended
+
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{Emitter, Manager};
+
+use super::types::{IdleAction, IdleStatus, IdleTimeouts, LockState};
+use crate::audit::{record_audit_event, AuditEvent, AuditEventKind};
+use crate::auth::SessionState;
+use crate::config::AuthConfig;
+use crate::tray::refresh_tray;
+
+/// How often the monitor checks idle time
+const TICK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Event emitted when the session is locked
+pub const LOCKED_EVENT: &str = "session://locked";
+
+/// Event emitted when the session is unlocked
+pub const UNLOCKED_EVENT: &str = "session://unlocked";
+
+/// Event emitted when the hard timeout logs the user out
+pub const TIMEOUT_EVENT: &str = "session://timeout";
+
+struct IdleInner {
+    last_activity: Instant,
+    /// Whether the frontend reported activity since the last reset
+    reported: bool,
+    state: LockState,
+}
+
+/// Managed state tracking the last user activity and the lock state
+pub struct IdleMonitor {
+    inner: Mutex<IdleInner>,
+    /// Thresholds from `config.auth`, updated when the config is saved
+    timeouts: Mutex<IdleTimeouts>,
+}
+
+impl Default for IdleMonitor {
+    fn default() -> Self {
+        Self {
+            inner: Mutex::new(IdleInner {
+                last_activity: Instant::now(),
+                reported: false,
+                state: LockState::Unlocked,
+            }),
+            timeouts: Mutex::new(IdleTimeouts::from_config(&AuthConfig::default())),
+        }
+    }
+}
+
+impl IdleMonitor {
+    /// Record input activity; ignored while locked so the lock screen cannot extend the session
+    pub fn record_activity(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.state == LockState::Unlocked {
+            inner.last_activity = Instant::now();
+            inner.reported = true;
+        }
+    }
+
+    /// Time since the last activity, using OS-wide idle time when it is shorter
+    ///
+    /// None when idle time cannot be told: there is no OS idle source on this
+    /// platform and the frontend has not reported any activity.
+    pub fn idle_duration(&self) -> Option<Duration> {
+        let inner = self.inner.lock().unwrap();
+        let app_idle = inner.last_activity.elapsed();
+        match os_idle_duration() {
+            Some(os_idle) => Some(app_idle.min(os_idle)),
+            None => inner.reported.then_some(app_idle),
+        }
+    }
+
+    pub fn timeouts(&self) -> IdleTimeouts {
+        *self.timeouts.lock().unwrap()
+    }
+
+    /// Take the lock and logout thresholds from the auth config
+    pub fn apply_config(&self, config: &AuthConfig) {
+        *self.timeouts.lock().unwrap() = IdleTimeouts::from_config(config);
+    }
+
+    pub fn state(&self) -> LockState {
+        self.inner.lock().unwrap().state
+    }
+
+    /// Lock the session, returning false if it was already locked
+    pub fn lock(&self) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        let changed = inner.state != LockState::Locked;
+        inner.state = LockState::Locked;
+        changed
+    }
+
+    /// Unlock the session and restart the idle clock
+    pub fn unlock(&self) {
+        self.reset();
+    }
+
+    /// Return to the unlocked state with a fresh idle clock
+    pub fn reset(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.state = LockState::Unlocked;
+        inner.last_activity = Instant::now();
+        inner.reported = false;
+    }
+
+    pub fn status(&self) -> IdleStatus {
+        let timeouts = self.timeouts();
+        IdleStatus {
+            state: self.state(),
+            idle_secs: self.idle_duration().unwrap_or_default().as_secs(),
+            lock_after_secs: timeouts.lock_after.map(|d| d.as_secs()),
+            logout_after_secs: timeouts.logout_after.map(|d| d.as_secs()),
+        }
+    }
+}
+
+/// Start the background thread that enforces idle timeouts
+pub fn start_idle_monitor(app: tauri::AppHandle) {
+    thread::spawn(move || loop {
+        thread::sleep(TICK_INTERVAL);
+        tick(&app);
+    });
+}
+
+fn tick(app: &tauri::AppHandle) {
+    let monitor = app.state::<IdleMonitor>();
+
+    // Idle time only counts while someone is signed in
+    if !app.state::<SessionState>().is_active() {
+        monitor.reset();
+        return;
+    }
+    let Some(idle) = monitor.idle_duration() else {
+        return;
+    };
+
+    match monitor.timeouts().evaluate(idle, monitor.state()) {
+        IdleAction::Lock => lock_session(app),
+        IdleAction::Logout => force_logout(app),
+        IdleAction::None => {}
+    }
+}
+
+/// Put the session behind the lock screen, keeping the main window open
+pub fn lock_session(app: &tauri::AppHandle) {
+    let monitor = app.state::<IdleMonitor>();
+    if monitor.lock() {
+        record_audit_event(
+            app,
+            AuditEvent::new(AuditEventKind::Lock)
+                .with_optional_user(app.state::<SessionState>().current_user().as_ref()),
+        );
+        let _ = app.emit(LOCKED_EVENT, monitor.status());
+        refresh_tray(app);
+    }
+}
+
+/// End the session after the hard timeout and return to the login window
+fn force_logout(app: &tauri::AppHandle) {
+    // Restart the idle clock now so the next tick does not log out again
+    // while the session is being torn down
+    app.state::<IdleMonitor>().reset();
+    let _ = app.emit(TIMEOUT_EVENT, ());
+
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let _ = crate::window::sign_out(&app, AuditEventKind::SessionTimeout).await;
+    });
+}
+
+#[cfg(windows)]
+fn os_idle_duration() -> Option<Duration> {
+    use windows_sys::Win32::System::SystemInformation::GetTickCount;
+    use windows_sys::Win32::UI::Input::KeyboardAndMouse::{GetLastInputInfo, LASTINPUTINFO};
+
+    let mut info = LASTINPUTINFO {
+        cbSize: std::mem::size_of::<LASTINPUTINFO>() as u32,
+        dwTime: 0,
+    };
+
+    // SAFETY: `info` is a valid LASTINPUTINFO with `cbSize` set as the API requires
+    if unsafe { GetLastInputInfo(&mut info) } == 0 {
+        return None;
+    }
+
+    // SAFETY: GetTickCount has no preconditions
+    let now = unsafe { GetTickCount() };
+    Some(Duration::from_millis(u64::from(
+        now.wrapping_sub(info.dwTime),
+    )))
+}
+
+#[cfg(not(windows))]
+fn os_idle_duration() -> Option<Duration> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(not(windows))]
+    fn test_idle_time_needs_activity_reports_without_os_source() {
+        let monitor = IdleMonitor::default();
+        assert!(monitor.idle_duration().is_none());
+
+        monitor.record_activity();
+        assert!(monitor.idle_duration().is_some());
+
+        monitor.reset();
+        assert!(monitor.idle_duration().is_none());
+    }
+}
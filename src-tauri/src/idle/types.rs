@@ -0,0 +1,108 @@
+//! Idle Types
+//! Data structures for idle tracking and session locking
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::config::AuthConfig;
+
+/// Whether the signed-in session is usable or behind the lock screen
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum LockState {
+    #[default]
+    Unlocked,
+    Locked,
+}
+
+/// Action the idle monitor should take after a tick
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IdleAction {
+    None,
+    Lock,
+    Logout,
+}
+
+/// Lock and logout thresholds; `None` disables the step
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct IdleTimeouts {
+    pub lock_after: Option<Duration>,
+    pub logout_after: Option<Duration>,
+}
+
+impl IdleTimeouts {
+    pub fn from_config(config: &AuthConfig) -> Self {
+        let minutes = |m: u32| (m > 0).then(|| Duration::from_secs(u64::from(m) * 60));
+        Self {
+            lock_after: minutes(config.idle_lock_minutes),
+            logout_after: minutes(config.idle_logout_minutes),
+        }
+    }
+
+    /// Decide what to do for the given idle time and lock state
+    pub fn evaluate(&self, idle: Duration, state: LockState) -> IdleAction {
+        if self.logout_after.is_some_and(|t| idle >= t) {
+            return IdleAction::Logout;
+        }
+        if state == LockState::Unlocked && self.lock_after.is_some_and(|t| idle >= t) {
+            return IdleAction::Lock;
+        }
+        IdleAction::None
+    }
+}
+
+/// Idle status reported to the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IdleStatus {
+    pub state: LockState,
+    pub idle_secs: u64,
+    pub lock_after_secs: Option<u64>,
+    pub logout_after_secs: Option<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timeouts() -> IdleTimeouts {
+        IdleTimeouts {
+            lock_after: Some(Duration::from_secs(60)),
+            logout_after: Some(Duration::from_secs(300)),
+        }
+    }
+
+    #[test]
+    fn test_evaluate_lock_then_logout() {
+        let t = timeouts();
+        assert_eq!(
+            t.evaluate(Duration::from_secs(10), LockState::Unlocked),
+            IdleAction::None
+        );
+        assert_eq!(
+            t.evaluate(Duration::from_secs(60), LockState::Unlocked),
+            IdleAction::Lock
+        );
+        assert_eq!(
+            t.evaluate(Duration::from_secs(120), LockState::Locked),
+            IdleAction::None
+        );
+        assert_eq!(
+            t.evaluate(Duration::from_secs(300), LockState::Locked),
+            IdleAction::Logout
+        );
+    }
+
+    #[test]
+    fn test_zero_minutes_disables() {
+        let config = AuthConfig {
+            idle_lock_minutes: 0,
+            idle_logout_minutes: 0,
+            ..Default::default()
+        };
+        let t = IdleTimeouts::from_config(&config);
+        assert_eq!(
+            t.evaluate(Duration::from_secs(86_400), LockState::Unlocked),
+            IdleAction::None
+        );
+    }
+}
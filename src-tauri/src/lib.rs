@@ -1,27 +1,140 @@
 // Core modules
+mod api;
 mod common;
 mod config;
+mod logging;
+mod tray;
 mod window;
 
 // Feature modules
+mod audit;
 mod auth;
+mod deep_link;
+mod device;
+mod diagnostics;
+mod idle;
+mod shortcuts;
 
-use auth::{clear_session, get_current_user, validate_token};
+use audit::{query_audit_log, report_card_tap, verify_audit_log, AuditLog};
+use auth::{
+    add_saved_account, begin_totp_enrollment, change_password, check_login_attempt,
+    check_password_strength, clear_login_lockouts, clear_session, confirm_password_reset,
+    confirm_totp_enrollment, disable_totp, get_current_user, get_mfa_status, get_pin_status,
+    list_saved_accounts, login, refresh_access_token, remove_pin, remove_saved_account,
+    reorder_saved_accounts, request_password_reset, restore_session, set_pin, start_session,
+    validate_token, verify_mfa, LoginThrottleState, MfaState, SessionState,
+};
+use common::set_error_language;
 use config::{
     get_app_language, get_app_theme, get_auto_start, get_config, get_nfc_enabled,
-    get_remember_me_default, get_window_state, greet, save_window_state, set_app_language,
-    set_app_theme, set_auto_start, set_config, set_nfc_enabled, set_remember_me_default,
+    get_remember_me_default, get_window_state, greet, load_config, save_window_state,
+    set_app_language, set_app_theme, set_auto_start, set_config, set_nfc_enabled,
+    set_remember_me_default,
+};
+use deep_link::{handle_deep_links, take_pending_deep_links, DeepLinkQueue};
+use device::{
+    get_device_context, list_device_registrations, revoke_device_registration,
+    sign_device_challenge, DeviceState,
+};
+use diagnostics::export_diagnostics;
+use idle::{
+    get_idle_status, lock_session, report_activity, start_idle_monitor, unlock_session, IdleMonitor,
+};
+use logging::{export_log_bundle, init_logging, log_frontend};
+use shortcuts::{
+    get_shortcuts, handle_shortcut, refresh_shortcuts, set_shortcut, ShortcutRegistry,
+};
+use tauri::Manager;
+use tauri_plugin_deep_link::DeepLinkExt;
+use tray::create_tray;
+use window::{
+    close_presentation_window, close_window, exit_kiosk, get_kiosk_status, get_presentation_status,
+    handle_second_instance, handle_window_event, list_monitors, list_windows, logout_to_auth,
+    minimize_window, move_presentation_window, open_main_window, open_presentation_window,
+    open_window, report_main_route, restore_geometry, resume_kiosk, set_kiosk_exit_password,
+    set_presentation_content, GeometryTracker, KioskState, PresentationState, TransitionState,
+    WindowRegistry,
 };
-use window::{close_window, logout_to_auth, minimize_window, open_main_window};
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
+        // Must be registered first so a second launch exits before doing any work
+        .plugin(tauri_plugin_single_instance::init(handle_second_instance))
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_autostart::init(
             tauri_plugin_autostart::MacosLauncher::LaunchAgent,
             Some(vec!["--autostarted"]),
         ))
+        .plugin(tauri_plugin_deep_link::init())
+        .plugin(
+            tauri_plugin_global_shortcut::Builder::new()
+                .with_handler(handle_shortcut)
+                .build(),
+        )
+        .manage(SessionState::default())
+        .manage(MfaState::default())
+        .manage(LoginThrottleState::default())
+        .manage(IdleMonitor::default())
+        .manage(DeviceState::default())
+        .manage(AuditLog::default())
+        .manage(GeometryTracker::default())
+        .manage(KioskState::default())
+        .manage(PresentationState::default())
+        .manage(WindowRegistry::default())
+        .manage(TransitionState::default())
+        .manage(ShortcutRegistry::default())
+        .manage(DeepLinkQueue::default())
+        .setup(|app| {
+            // A session stored with a PIN resumes behind the lock screen,
+            // but only on the device it was started on
+            let handle = app.handle();
+            init_logging(handle);
+            let config = load_config(handle);
+            set_error_language(&config.language);
+            app.state::<IdleMonitor>().apply_config(&config.auth);
+            log::info!("Starting Smart School {}", app.package_info().version);
+
+            let restored = app
+                .state::<DeviceState>()
+                .identity(handle)
+                .and_then(|identity| {
+                    restore_session(handle, &app.state::<SessionState>(), &identity.device_id)
+                })
+                .unwrap_or(false);
+            if restored {
+                app.state::<IdleMonitor>().lock();
+            }
+
+            // The login window starts hidden so its saved position applies without a jump
+            if let Some(auth_window) = app.get_webview_window("auth") {
+                let _ = restore_geometry(&auth_window);
+                auth_window.show()?;
+            }
+
+            // The app stays usable without a tray (e.g. desktops without a status area)
+            if let Err(e) = create_tray(handle) {
+                log::warn!("Tray icon unavailable: {}", e);
+            }
+
+            refresh_shortcuts(handle);
+
+            // Links that launched the app wait for sign-in; later ones come
+            // from the OS or a second launch
+            #[cfg(any(windows, target_os = "linux"))]
+            let _ = app.deep_link().register_all();
+            if let Ok(Some(urls)) = app.deep_link().get_current() {
+                handle_deep_links(handle, urls.iter().map(|url| url.as_str()));
+            }
+            let listener = handle.clone();
+            app.deep_link().on_open_url(move |event| {
+                handle_deep_links(&listener, event.urls().iter().map(|url| url.as_str()));
+            });
+
+            start_idle_monitor(handle.clone());
+            Ok(())
+        })
+        .on_window_event(handle_window_event)
         .invoke_handler(tauri::generate_handler![
             greet,
             // Config commands
@@ -44,10 +157,68 @@ pub fn run() {
             logout_to_auth,
             close_window,
             minimize_window,
+            open_window,
+            list_windows,
+            get_kiosk_status,
+            set_kiosk_exit_password,
+            exit_kiosk,
+            resume_kiosk,
+            list_monitors,
+            open_presentation_window,
+            move_presentation_window,
+            set_presentation_content,
+            report_main_route,
+            close_presentation_window,
+            get_presentation_status,
             // Auth commands
             validate_token,
+            login,
+            refresh_access_token,
             get_current_user,
+            start_session,
             clear_session,
+            list_saved_accounts,
+            add_saved_account,
+            remove_saved_account,
+            reorder_saved_accounts,
+            check_login_attempt,
+            clear_login_lockouts,
+            set_pin,
+            remove_pin,
+            get_pin_status,
+            verify_mfa,
+            begin_totp_enrollment,
+            confirm_totp_enrollment,
+            disable_totp,
+            get_mfa_status,
+            check_password_strength,
+            change_password,
+            request_password_reset,
+            confirm_password_reset,
+            // Audit trail
+            query_audit_log,
+            verify_audit_log,
+            report_card_tap,
+            // Device identity
+            get_device_context,
+            sign_device_challenge,
+            list_device_registrations,
+            revoke_device_registration,
+            // Idle / lock screen
+            report_activity,
+            lock_session,
+            unlock_session,
+            get_idle_status,
+            // Deep links
+            take_pending_deep_links,
+            // Global shortcuts
+            get_shortcuts,
+            set_shortcut,
+            // Logging
+            log_frontend,
+            export_log_bundle,
+            // Diagnostics
+            export_diagnostics,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,87 @@
+//! Logging Commands
+//! Frontend log forwarding and support bundles
+
+use std::fs;
+
+use super::file::recent_log_files;
+use super::logger::{bundle_path, flush_logs, log_dir, parse_frontend_level};
+use crate::common::{write_zip, AppError, AppResult, ErrorCode};
+use crate::diagnostics::redact_log;
+
+/// Longest frontend message kept, in characters
+const MAX_FRONTEND_MESSAGE: usize = 4000;
+
+/// Days of logs bundled when none are requested
+pub const DEFAULT_BUNDLE_DAYS: u32 = 7;
+
+/// Keep component names from the frontend to a plain `a::b` path
+fn sanitize_target(target: &str) -> String {
+    target
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | ':' | '.'))
+        .take(64)
+        .collect()
+}
+
+/// Write a frontend message to the log under `frontend::<window>[::<target>]`
+#[tauri::command]
+pub fn log_frontend(
+    window: tauri::Window,
+    level: String,
+    message: String,
+    target: Option<String>,
+) -> AppResult<()> {
+    let level = parse_frontend_level(&level).ok_or_else(|| {
+        AppError::invalid_input(format!("Invalid log level: {}", level))
+            .with_code(ErrorCode::LogInvalidLevel)
+            .with_param("level", &level)
+    })?;
+
+    let mut full_target = format!("frontend::{}", window.label());
+    if let Some(target) = target.as_deref().map(sanitize_target) {
+        if !target.is_empty() {
+            full_target = format!("{}::{}", full_target, target);
+        }
+    }
+    let message = message
+        .chars()
+        .take(MAX_FRONTEND_MESSAGE)
+        .collect::<String>();
+
+    log::log!(target: &full_target, level, "{}", message);
+    Ok(())
+}
+
+/// Zip the log files touched in the last `days` days (default 7) and return the archive path
+/// Logs are redacted the same way as in the diagnostics bundle
+#[tauri::command]
+pub fn export_log_bundle(days: Option<u32>) -> AppResult<String> {
+    let dir = log_dir().ok_or_else(|| {
+        AppError::not_found("Logging to file is not available")
+            .with_code(ErrorCode::LogDirUnavailable)
+    })?;
+    flush_logs();
+
+    let files = recent_log_files(&dir, days.unwrap_or(DEFAULT_BUNDLE_DAYS))
+        .into_iter()
+        .map(|path| {
+            let name = path.file_name().unwrap().to_string_lossy().into_owned();
+            fs::read_to_string(&path).map(|content| (name, redact_log(&content)))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let bundle = bundle_path(&dir, "logs");
+    write_zip(
+        &bundle,
+        files
+            .iter()
+            .map(|(name, content)| (name.as_str(), content.as_bytes())),
+    )?;
+
+    log::info!(
+        "Exported log bundle with {} files to {}",
+        files.len(),
+        bundle.display()
+    );
+    Ok(bundle.to_string_lossy().into_owned())
+}
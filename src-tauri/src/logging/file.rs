@@ -0,0 +1,169 @@
+//! Rotating Log File
+//! Appends lines to `smart-school.log`, shifting it to `smart-school.1.log` and so on when full
+
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Base name of the log files
+pub const LOG_FILE_STEM: &str = "smart-school";
+
+/// A log file rotated by size
+pub struct RotatingFile {
+    dir: PathBuf,
+    max_size: u64,
+    /// Rotated files kept besides the current one
+    max_files: u32,
+    file: Option<File>,
+    size: u64,
+}
+
+impl RotatingFile {
+    pub fn new(dir: PathBuf, max_size: u64, max_files: u32) -> Self {
+        Self {
+            dir,
+            max_size,
+            max_files,
+            file: None,
+            size: 0,
+        }
+    }
+
+    pub fn set_limits(&mut self, max_size: u64, max_files: u32) {
+        self.max_size = max_size;
+        self.max_files = max_files;
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// `smart-school.log` for the current file, `smart-school.<n>.log` for rotated ones
+    fn path(&self, index: u32) -> PathBuf {
+        match index {
+            0 => self.dir.join(format!("{}.log", LOG_FILE_STEM)),
+            n => self.dir.join(format!("{}.{}.log", LOG_FILE_STEM, n)),
+        }
+    }
+
+    fn open(&mut self) -> io::Result<&mut File> {
+        if self.file.is_none() {
+            fs::create_dir_all(&self.dir)?;
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(self.path(0))?;
+            self.size = file.metadata()?.len();
+            self.file = Some(file);
+        }
+        Ok(self.file.as_mut().unwrap())
+    }
+
+    /// Shift every file up one index, dropping the oldest
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file = None;
+        self.size = 0;
+
+        let oldest = self.path(self.max_files);
+        if oldest.exists() {
+            fs::remove_file(oldest)?;
+        }
+        for index in (0..self.max_files).rev() {
+            let from = self.path(index);
+            if from.exists() {
+                fs::rename(from, self.path(index + 1))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Append a line, rotating first if it would overflow the current file
+    pub fn write_line(&mut self, line: &str) -> io::Result<()> {
+        self.open()?;
+        let len = line.len() as u64 + 1;
+        if self.size > 0 && self.size + len > self.max_size {
+            self.rotate()?;
+        }
+
+        let file = self.open()?;
+        file.write_all(line.as_bytes())?;
+        file.write_all(b"\n")?;
+        self.size += len;
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> io::Result<()> {
+        match &mut self.file {
+            Some(file) => file.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Log files in a directory, newest first
+pub fn log_files(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut files = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with(LOG_FILE_STEM) && name.ends_with(".log"))
+        })
+        .collect::<Vec<_>>();
+    files.sort_by_key(|path| {
+        std::cmp::Reverse(
+            fs::metadata(path)
+                .and_then(|m| m.modified())
+                .unwrap_or(SystemTime::UNIX_EPOCH),
+        )
+    });
+    files
+}
+
+/// Log files modified in the last `days` days, newest first
+pub fn recent_log_files(dir: &Path, days: u32) -> Vec<PathBuf> {
+    let since = SystemTime::now() - Duration::from_secs(days as u64 * 24 * 60 * 60);
+    log_files(dir)
+        .into_iter()
+        .filter(|path| {
+            fs::metadata(path)
+                .and_then(|m| m.modified())
+                .is_ok_and(|modified| modified >= since)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rotates_and_drops_oldest() {
+        let dir = std::env::temp_dir().join(format!("smart-school-logs-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+
+        // Each line is 10 bytes with its newline, so every file holds two
+        let mut file = RotatingFile::new(dir.clone(), 20, 2);
+        for n in 0..7 {
+            file.write_line(&format!("line-{:04}", n)).unwrap();
+        }
+        file.flush().unwrap();
+
+        let read = |name: &str| fs::read_to_string(dir.join(name)).unwrap();
+        assert_eq!(read("smart-school.log"), "line-0006\n");
+        assert_eq!(read("smart-school.1.log"), "line-0004\nline-0005\n");
+        assert_eq!(read("smart-school.2.log"), "line-0002\nline-0003\n");
+        assert!(!dir.join("smart-school.3.log").exists());
+        assert_eq!(log_files(&dir).len(), 3);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
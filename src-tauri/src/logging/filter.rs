@@ -0,0 +1,120 @@
+//! Log Filter
+//! Default level with per-module overrides, matched on the longest module prefix
+
+use log::LevelFilter;
+use std::str::FromStr;
+
+use crate::config::LoggingConfig;
+
+/// Levels by log target
+#[derive(Debug, Clone, PartialEq)]
+pub struct LogFilter {
+    default: LevelFilter,
+    /// Sorted longest prefix first so the most specific module wins
+    modules: Vec<(String, LevelFilter)>,
+}
+
+impl Default for LogFilter {
+    fn default() -> Self {
+        Self {
+            default: LevelFilter::Info,
+            modules: Vec::new(),
+        }
+    }
+}
+
+/// Parse a level name, falling back when it is not one of off/error/warn/info/debug/trace
+fn parse_level(level: &str, fallback: LevelFilter) -> LevelFilter {
+    LevelFilter::from_str(level.trim()).unwrap_or(fallback)
+}
+
+/// Whether `target` is `module` or one of its submodules
+fn matches_module(target: &str, module: &str) -> bool {
+    target
+        .strip_prefix(module)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+}
+
+impl LogFilter {
+    pub fn from_config(config: &LoggingConfig) -> Self {
+        let default = parse_level(&config.level, LevelFilter::Info);
+        let mut modules = config
+            .modules
+            .iter()
+            .map(|(module, level)| (module.clone(), parse_level(level, default)))
+            .collect::<Vec<_>>();
+        modules.sort_by_key(|(module, _)| std::cmp::Reverse(module.len()));
+
+        Self { default, modules }
+    }
+
+    /// Level enabled for a log target
+    pub fn level_for(&self, target: &str) -> LevelFilter {
+        self.modules
+            .iter()
+            .find(|(module, _)| matches_module(target, module))
+            .map(|(_, level)| *level)
+            .unwrap_or(self.default)
+    }
+
+    /// Most verbose level any target can log at
+    pub fn max_level(&self) -> LevelFilter {
+        self.modules
+            .iter()
+            .map(|(_, level)| *level)
+            .fold(self.default, Ord::max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filter(level: &str, modules: &[(&str, &str)]) -> LogFilter {
+        LogFilter::from_config(&LoggingConfig {
+            level: level.to_string(),
+            modules: modules
+                .iter()
+                .map(|(m, l)| (m.to_string(), l.to_string()))
+                .collect(),
+            ..LoggingConfig::default()
+        })
+    }
+
+    #[test]
+    fn test_most_specific_module_wins() {
+        let filter = filter(
+            "warn",
+            &[
+                ("tauri_app_lib::auth", "debug"),
+                ("tauri_app_lib::auth::mfa", "error"),
+            ],
+        );
+
+        assert_eq!(
+            filter.level_for("tauri_app_lib::auth::session"),
+            LevelFilter::Debug
+        );
+        assert_eq!(
+            filter.level_for("tauri_app_lib::auth::mfa"),
+            LevelFilter::Error
+        );
+        assert_eq!(filter.level_for("tauri_app_lib::auth"), LevelFilter::Debug);
+        assert_eq!(filter.level_for("tauri_app_lib::idle"), LevelFilter::Warn);
+        assert_eq!(filter.max_level(), LevelFilter::Debug);
+    }
+
+    #[test]
+    fn test_prefix_matches_whole_segments() {
+        let filter = filter("info", &[("frontend", "off")]);
+        assert_eq!(filter.level_for("frontend::main"), LevelFilter::Off);
+        assert_eq!(filter.level_for("frontends"), LevelFilter::Info);
+    }
+
+    #[test]
+    fn test_invalid_levels_fall_back() {
+        let filter = filter("loud", &[("tauri_app_lib::tray", "nope")]);
+        assert_eq!(filter.level_for("anything"), LevelFilter::Info);
+        assert_eq!(filter.level_for("tauri_app_lib::tray"), LevelFilter::Info);
+    }
+}
@@ -0,0 +1,180 @@
+//! Logger
+//! `log` backend writing one JSON object per line, reconfigured when the config changes
+
+use chrono::{DateTime, Local, SecondsFormat, Utc};
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock, RwLock};
+use tauri::Manager;
+
+use super::file::RotatingFile;
+use super::filter::LogFilter;
+use crate::config::{load_config, LoggingConfig};
+
+/// One line of the log file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogEntry {
+    pub ts: DateTime<Utc>,
+    pub level: String,
+    pub target: String,
+    pub message: String,
+}
+
+impl LogEntry {
+    fn from_record(record: &Record) -> Self {
+        Self {
+            ts: Utc::now(),
+            level: record.level().to_string(),
+            target: record.target().to_string(),
+            message: record.args().to_string(),
+        }
+    }
+}
+
+/// Warnings and errors kept in memory for diagnostics
+const RECENT_PROBLEMS_KEPT: usize = 50;
+
+struct JsonLogger {
+    filter: RwLock<LogFilter>,
+    /// None until the log directory is known
+    file: Mutex<Option<RotatingFile>>,
+    recent_problems: Mutex<VecDeque<LogEntry>>,
+}
+
+static LOGGER: OnceLock<JsonLogger> = OnceLock::new();
+
+impl Log for JsonLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.filter.read().unwrap().level_for(metadata.target())
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let entry = LogEntry::from_record(record);
+        if cfg!(debug_assertions) {
+            eprintln!(
+                "{} {:<5} {} {}",
+                entry.ts.to_rfc3339_opts(SecondsFormat::Millis, true),
+                entry.level,
+                entry.target,
+                entry.message
+            );
+        }
+
+        if let Ok(line) = serde_json::to_string(&entry) {
+            if let Some(file) = self.file.lock().unwrap().as_mut() {
+                // Nowhere left to report a failing log file
+                let _ = file.write_line(&line);
+            }
+        }
+
+        if record.level() <= Level::Warn {
+            let mut recent = self.recent_problems.lock().unwrap();
+            if recent.len() == RECENT_PROBLEMS_KEPT {
+                recent.pop_front();
+            }
+            recent.push_back(entry);
+        }
+    }
+
+    fn flush(&self) {
+        if let Some(file) = self.file.lock().unwrap().as_mut() {
+            let _ = file.flush();
+        }
+    }
+}
+
+fn logger() -> &'static JsonLogger {
+    LOGGER.get_or_init(|| JsonLogger {
+        filter: RwLock::new(LogFilter::default()),
+        file: Mutex::new(None),
+        recent_problems: Mutex::new(VecDeque::new()),
+    })
+}
+
+/// Install the logger, writing to the app log directory
+///
+/// Called first in setup; logging before this point is discarded.
+pub fn init_logging(app: &tauri::AppHandle) {
+    let logger = logger();
+    if log::set_logger(logger).is_err() {
+        return;
+    }
+    log::set_max_level(LevelFilter::Info);
+
+    match app.path().app_log_dir() {
+        Ok(dir) => {
+            *logger.file.lock().unwrap() = Some(RotatingFile::new(dir, 0, 0));
+        }
+        Err(e) => eprintln!("No log directory, logging to file disabled: {}", e),
+    }
+    apply_logging_config(&load_config(app).logging);
+}
+
+/// Apply levels and rotation limits from the config
+pub fn apply_logging_config(config: &LoggingConfig) {
+    let logger = logger();
+    let filter = LogFilter::from_config(config);
+    log::set_max_level(filter.max_level());
+    *logger.filter.write().unwrap() = filter;
+
+    if let Some(file) = logger.file.lock().unwrap().as_mut() {
+        file.set_limits(config.max_file_size_kb.max(1) * 1024, config.max_files);
+    }
+}
+
+/// Directory the log files are written to, once logging is initialized
+pub fn log_dir() -> Option<PathBuf> {
+    let logger = LOGGER.get()?;
+    let file = logger.file.lock().unwrap();
+    file.as_ref().map(|file| file.dir().to_path_buf())
+}
+
+/// Most recent warnings and errors, oldest first
+pub fn recent_problems() -> Vec<LogEntry> {
+    LOGGER
+        .get()
+        .map(|logger| {
+            logger
+                .recent_problems
+                .lock()
+                .unwrap()
+                .iter()
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Timestamped archive path under `<log dir>/bundles`, e.g. `smart-school-logs-20240101-080000.zip`
+pub fn bundle_path(log_dir: &Path, kind: &str) -> PathBuf {
+    log_dir.join("bundles").join(format!(
+        "smart-school-{}-{}.zip",
+        kind,
+        Local::now().format("%Y%m%d-%H%M%S")
+    ))
+}
+
+/// Write buffered lines out before the files are read
+pub fn flush_logs() {
+    if let Some(logger) = LOGGER.get() {
+        logger.flush();
+    }
+}
+
+/// Level from a name sent by the frontend ("warning" is accepted for `console.warn`)
+pub fn parse_frontend_level(level: &str) -> Option<Level> {
+    match level.to_ascii_lowercase().as_str() {
+        "error" => Some(Level::Error),
+        "warn" | "warning" => Some(Level::Warn),
+        "info" | "log" => Some(Level::Info),
+        "debug" => Some(Level::Debug),
+        "trace" => Some(Level::Trace),
+        _ => None,
+    }
+}
@@ -0,0 +1,15 @@
+//! Logging Module
+//! Leveled, per-module filtered logging to rotating JSON-lines files in the app log directory
+
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+mod commands;
+mod file;
+mod filter;
+mod logger;
+
+pub use commands::*;
+pub use file::*;
+pub use filter::*;
+pub use logger::*;
@@ -0,0 +1,71 @@
+//! Shortcut Commands
+//! Tauri commands for viewing and customizing global shortcuts
+
+use super::registry::{
+    refresh_shortcuts, reserved_sequences, resolve_shortcuts, shortcut_overview,
+};
+use super::types::{BindingStatus, ShortcutAction, ShortcutOverview};
+use crate::common::{AppError, AppResult, ErrorCode};
+use crate::config::{load_config, save_config, ShortcutsConfig};
+
+/// Get every shortcut action with its keys and status for the signed-in user
+#[tauri::command]
+pub fn get_shortcuts(app: tauri::AppHandle) -> ShortcutOverview {
+    shortcut_overview(&app)
+}
+
+/// Change the keys of an action
+/// `None` restores the default, an empty string disables the action
+#[tauri::command]
+pub fn set_shortcut(
+    app: tauri::AppHandle,
+    action: ShortcutAction,
+    accelerator: Option<String>,
+) -> AppResult<ShortcutOverview> {
+    let mut config = load_config(&app);
+    match accelerator {
+        Some(accelerator) => config
+            .shortcuts
+            .overrides
+            .insert(action.id().to_string(), accelerator.trim().to_string()),
+        None => config.shortcuts.overrides.remove(action.id()),
+    };
+
+    // Reject unusable keys before anything is saved, even while shortcuts are off
+    let probe = ShortcutsConfig {
+        enabled: true,
+        ..config.shortcuts.clone()
+    };
+    let resolved = resolve_shortcuts(&probe, &reserved_sequences(&config), None);
+    let binding = resolved
+        .bindings
+        .iter()
+        .find(|b| b.action == action)
+        .ok_or_else(|| {
+            AppError::not_found("Unknown shortcut action")
+                .with_code(ErrorCode::ShortcutUnknownAction)
+        })?;
+    let accelerator = binding.accelerator.as_deref().unwrap_or_default();
+    match binding.status {
+        BindingStatus::Invalid => {
+            return Err(
+                AppError::invalid_input(format!("Invalid shortcut: {}", accelerator))
+                    .with_code(ErrorCode::ShortcutInvalid)
+                    .with_param("accelerator", accelerator),
+            )
+        }
+        BindingStatus::Conflict => {
+            return Err(AppError::invalid_input(format!(
+                "{} is already used by another shortcut",
+                accelerator
+            ))
+            .with_code(ErrorCode::ShortcutConflict)
+            .with_param("accelerator", accelerator));
+        }
+        _ => {}
+    }
+
+    save_config(&app, &config)?;
+    refresh_shortcuts(&app);
+    Ok(shortcut_overview(&app))
+}
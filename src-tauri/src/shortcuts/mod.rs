@@ -0,0 +1,13 @@
+//! Shortcuts Module
+//! Global keyboard shortcuts for teachers, with per-role availability and user overrides
+
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+mod commands;
+mod registry;
+mod types;
+
+pub use commands::*;
+pub use registry::*;
+pub use types::*;
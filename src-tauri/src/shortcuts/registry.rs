@@ -0,0 +1,361 @@
+//! Shortcut Registry
+//! Resolves bindings from config, registers them with the OS and dispatches presses
+
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutEvent, ShortcutState};
+
+use super::types::*;
+use crate::auth::{SessionState, UserRoleType};
+use crate::config::{load_config, AppConfig, ShortcutsConfig};
+use crate::idle::{lock_session, IdleMonitor, LockState};
+use crate::window::{kiosk_active, toggle_presentation_window, KIOSK_EXIT_REQUESTED_EVENT};
+
+/// Event sent to the main window for actions handled by the frontend
+pub const SHORTCUT_TRIGGERED_EVENT: &str = "shortcut://triggered";
+
+/// Reserved name for keys another application already holds
+const SYSTEM_RESERVED: &str = "system";
+
+/// Managed state holding the shortcuts registered with the OS
+#[derive(Default)]
+pub struct ShortcutRegistry {
+    active: Mutex<Vec<(Shortcut, ShortcutAction)>>,
+    /// Actions whose keys the OS refused, usually because another app holds them
+    rejected: Mutex<Vec<ShortcutAction>>,
+    /// Kiosk escape sequence, registered while kiosk mode is active
+    kiosk_escape: Mutex<Option<Shortcut>>,
+}
+
+fn parse_accelerator(accelerator: &str) -> Option<Shortcut> {
+    accelerator.parse().ok()
+}
+
+/// Key sequences the app uses elsewhere, by name
+pub fn reserved_sequences(config: &AppConfig) -> Vec<(&'static str, String)> {
+    let mut reserved = Vec::new();
+    if config.kiosk.enabled {
+        reserved.push(("kiosk_escape", config.kiosk.escape_sequence.clone()));
+    }
+    reserved
+}
+
+/// Resolve the binding of every action
+///
+/// Overrides replace defaults; actions sharing keys with each other or with
+/// a reserved sequence are all reported as conflicts and none of them is
+/// registered. `role` is the signed-in user's role, if anyone is signed in.
+pub fn resolve_shortcuts(
+    config: &ShortcutsConfig,
+    reserved: &[(&str, String)],
+    role: Option<&UserRoleType>,
+) -> ShortcutOverview {
+    let configured: Vec<(ShortcutAction, String, bool)> = ShortcutAction::ALL
+        .into_iter()
+        .map(|action| match config.overrides.get(action.id()) {
+            Some(accelerator) => (action, accelerator.trim().to_string(), true),
+            None => (action, action.default_accelerator().to_string(), false),
+        })
+        .collect();
+    let parsed: Vec<(ShortcutAction, Shortcut, &str)> = configured
+        .iter()
+        .filter_map(|(action, accelerator, _)| {
+            parse_accelerator(accelerator).map(|shortcut| (*action, shortcut, accelerator.as_str()))
+        })
+        .collect();
+
+    let mut conflicts: Vec<(Shortcut, ShortcutConflict)> = Vec::new();
+    for (action, shortcut, accelerator) in &parsed {
+        let shared = parsed.iter().filter(|(_, s, _)| s == shortcut).count() > 1;
+        let reserved = reserved
+            .iter()
+            .find(|(_, sequence)| parse_accelerator(sequence).as_ref() == Some(shortcut))
+            .map(|(name, _)| name.to_string());
+        if !shared && reserved.is_none() {
+            continue;
+        }
+
+        match conflicts.iter_mut().find(|(s, _)| s == shortcut) {
+            Some((_, conflict)) => conflict.actions.push(*action),
+            None => conflicts.push((
+                *shortcut,
+                ShortcutConflict {
+                    accelerator: accelerator.to_string(),
+                    actions: vec![*action],
+                    reserved,
+                },
+            )),
+        }
+    }
+
+    let bindings = configured
+        .into_iter()
+        .map(|(action, accelerator, customized)| {
+            let shortcut = parse_accelerator(&accelerator);
+            let status = if !config.enabled || accelerator.is_empty() {
+                BindingStatus::Disabled
+            } else if shortcut.is_none() {
+                BindingStatus::Invalid
+            } else if conflicts.iter().any(|(_, c)| c.actions.contains(&action)) {
+                BindingStatus::Conflict
+            } else if !role.is_some_and(|role| action.allowed_for(role)) {
+                BindingStatus::Unavailable
+            } else {
+                BindingStatus::Active
+            };
+
+            ShortcutBinding {
+                action,
+                accelerator: (!accelerator.is_empty()).then_some(accelerator),
+                customized,
+                dispatch: action.dispatch(),
+                status,
+            }
+        })
+        .collect();
+
+    ShortcutOverview {
+        enabled: config.enabled,
+        bindings,
+        conflicts: conflicts.into_iter().map(|(_, c)| c).collect(),
+    }
+}
+
+/// Resolve bindings for the signed-in user, including keys the OS refused
+pub fn shortcut_overview(app: &AppHandle) -> ShortcutOverview {
+    let config = load_config(app);
+    let role = app
+        .state::<SessionState>()
+        .current_user()
+        .map(|user| user.role_type);
+    let mut overview = resolve_shortcuts(
+        &config.shortcuts,
+        &reserved_sequences(&config),
+        role.as_ref(),
+    );
+
+    let rejected = app
+        .state::<ShortcutRegistry>()
+        .rejected
+        .lock()
+        .unwrap()
+        .clone();
+    for binding in &mut overview.bindings {
+        if binding.status == BindingStatus::Active && rejected.contains(&binding.action) {
+            binding.status = BindingStatus::Conflict;
+            overview.conflicts.push(ShortcutConflict {
+                accelerator: binding.accelerator.clone().unwrap_or_default(),
+                actions: vec![binding.action],
+                reserved: Some(SYSTEM_RESERVED.to_string()),
+            });
+        }
+    }
+    overview
+}
+
+/// Register the active bindings with the OS, replacing previous ones
+///
+/// Called whenever the signed-in user or the shortcut settings change.
+pub fn refresh_shortcuts(app: &AppHandle) {
+    let registry = app.state::<ShortcutRegistry>();
+    registry.rejected.lock().unwrap().clear();
+    let overview = shortcut_overview(app);
+
+    let global = app.global_shortcut();
+    let _ = global.unregister_all();
+
+    let mut active = Vec::new();
+    let mut rejected = Vec::new();
+    for binding in overview.bindings {
+        if binding.status != BindingStatus::Active {
+            continue;
+        }
+        let Some(shortcut) = binding.accelerator.as_deref().and_then(parse_accelerator) else {
+            continue;
+        };
+        if global.register(shortcut).is_ok() {
+            active.push((shortcut, binding.action));
+        } else {
+            log::warn!(
+                "Could not register {:?} shortcut {}",
+                binding.action,
+                binding.accelerator.as_deref().unwrap_or_default()
+            );
+            rejected.push(binding.action);
+        }
+    }
+
+    *registry.active.lock().unwrap() = active;
+    *registry.rejected.lock().unwrap() = rejected;
+
+    // Actions sharing the escape keys are reported as conflicts and never
+    // registered, so the escape sequence always wins
+    let mut escape = None;
+    if kiosk_active(app) {
+        let sequence = load_config(app).kiosk.escape_sequence;
+        match parse_accelerator(&sequence) {
+            Some(shortcut) if global.register(shortcut).is_ok() => escape = Some(shortcut),
+            _ => log::warn!("Could not register kiosk escape sequence {}", sequence),
+        }
+    }
+    *registry.kiosk_escape.lock().unwrap() = escape;
+}
+
+/// Called by the global shortcut plugin on key presses
+pub fn handle_shortcut(app: &AppHandle, shortcut: &Shortcut, event: ShortcutEvent) {
+    if event.state != ShortcutState::Pressed {
+        return;
+    }
+    let registry = app.state::<ShortcutRegistry>();
+    let is_escape = registry.kiosk_escape.lock().unwrap().as_ref() == Some(shortcut);
+    let action = registry
+        .active
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|(s, _)| s == shortcut)
+        .map(|(_, action)| *action);
+
+    // Nothing runs behind the lock screen
+    if app.state::<IdleMonitor>().state() == LockState::Locked {
+        return;
+    }
+
+    if is_escape {
+        if let Some(main_window) = app.get_webview_window("main") {
+            let _ = main_window.set_focus();
+        }
+        let _ = app.emit_to("main", KIOSK_EXIT_REQUESTED_EVENT, ());
+        return;
+    }
+    let Some(action) = action else {
+        return;
+    };
+
+    match action {
+        ShortcutAction::LockScreen => lock_session(app.clone()),
+        ShortcutAction::TogglePresentation => {
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                let _ = toggle_presentation_window(&app).await;
+            });
+        }
+        ShortcutAction::MuteStudentScreens | ShortcutAction::QuickScreenshot => {
+            let _ = app.emit_to("main", SHORTCUT_TRIGGERED_EVENT, action);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(overrides: &[(&str, &str)]) -> ShortcutsConfig {
+        ShortcutsConfig {
+            enabled: true,
+            overrides: overrides
+                .iter()
+                .map(|(id, accelerator)| (id.to_string(), accelerator.to_string()))
+                .collect(),
+        }
+    }
+
+    fn status(overview: &ShortcutOverview, action: ShortcutAction) -> BindingStatus {
+        overview
+            .bindings
+            .iter()
+            .find(|b| b.action == action)
+            .unwrap()
+            .status
+    }
+
+    #[test]
+    fn test_defaults_follow_role() {
+        let teacher = resolve_shortcuts(&config(&[]), &[], Some(&UserRoleType::Teacher));
+        assert!(teacher.conflicts.is_empty());
+        assert!(teacher
+            .bindings
+            .iter()
+            .all(|b| b.status == BindingStatus::Active));
+
+        let student = resolve_shortcuts(&config(&[]), &[], Some(&UserRoleType::Student));
+        assert_eq!(
+            status(&student, ShortcutAction::LockScreen),
+            BindingStatus::Active
+        );
+        assert_eq!(
+            status(&student, ShortcutAction::TogglePresentation),
+            BindingStatus::Unavailable
+        );
+
+        let nobody = resolve_shortcuts(&config(&[]), &[], None);
+        assert_eq!(
+            status(&nobody, ShortcutAction::LockScreen),
+            BindingStatus::Unavailable
+        );
+    }
+
+    #[test]
+    fn test_overrides_disable_or_replace() {
+        let overview = resolve_shortcuts(
+            &config(&[("quick_screenshot", ""), ("lock_screen", "Ctrl+Shift+F12")]),
+            &[],
+            Some(&UserRoleType::Teacher),
+        );
+        assert_eq!(
+            status(&overview, ShortcutAction::QuickScreenshot),
+            BindingStatus::Disabled
+        );
+        let lock = &overview.bindings[0];
+        assert!(lock.customized);
+        assert_eq!(lock.accelerator.as_deref(), Some("Ctrl+Shift+F12"));
+        assert_eq!(lock.status, BindingStatus::Active);
+
+        let invalid = resolve_shortcuts(
+            &config(&[("lock_screen", "Ctrl+Nope")]),
+            &[],
+            Some(&UserRoleType::Teacher),
+        );
+        assert_eq!(
+            status(&invalid, ShortcutAction::LockScreen),
+            BindingStatus::Invalid
+        );
+    }
+
+    #[test]
+    fn test_detects_conflicts() {
+        // Same keys written differently still collide
+        let overview = resolve_shortcuts(
+            &config(&[("quick_screenshot", "alt+cmdorctrl+p")]),
+            &[],
+            Some(&UserRoleType::Teacher),
+        );
+        assert_eq!(overview.conflicts.len(), 1);
+        assert_eq!(
+            overview.conflicts[0].actions,
+            vec![
+                ShortcutAction::TogglePresentation,
+                ShortcutAction::QuickScreenshot
+            ]
+        );
+        assert_eq!(
+            status(&overview, ShortcutAction::TogglePresentation),
+            BindingStatus::Conflict
+        );
+
+        let reserved = [("kiosk_escape", "Ctrl+Alt+Shift+K".to_string())];
+        let overview = resolve_shortcuts(
+            &config(&[("lock_screen", "Ctrl+Shift+Alt+K")]),
+            &reserved,
+            Some(&UserRoleType::Teacher),
+        );
+        assert_eq!(
+            overview.conflicts[0].reserved.as_deref(),
+            Some("kiosk_escape")
+        );
+        assert_eq!(
+            status(&overview, ShortcutAction::LockScreen),
+            BindingStatus::Conflict
+        );
+    }
+}
@@ -0,0 +1,123 @@
+//! Shortcut Types
+//! Shortcut actions, their defaults and the resolved bindings shown to the frontend
+
+use serde::{Deserialize, Serialize};
+
+use crate::auth::UserRoleType;
+
+/// Something a global shortcut can do
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum ShortcutAction {
+    LockScreen,
+    TogglePresentation,
+    MuteStudentScreens,
+    QuickScreenshot,
+}
+
+/// Where a triggered action is handled
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ShortcutDispatch {
+    /// Performed in Rust
+    Backend,
+    /// Sent to the main window as a `shortcut://triggered` event
+    Frontend,
+}
+
+impl ShortcutAction {
+    pub const ALL: [ShortcutAction; 4] = [
+        ShortcutAction::LockScreen,
+        ShortcutAction::TogglePresentation,
+        ShortcutAction::MuteStudentScreens,
+        ShortcutAction::QuickScreenshot,
+    ];
+
+    /// Key used in `shortcuts.overrides`
+    pub fn id(self) -> &'static str {
+        match self {
+            ShortcutAction::LockScreen => "lock_screen",
+            ShortcutAction::TogglePresentation => "toggle_presentation",
+            ShortcutAction::MuteStudentScreens => "mute_student_screens",
+            ShortcutAction::QuickScreenshot => "quick_screenshot",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|action| action.id() == id)
+    }
+
+    pub fn default_accelerator(self) -> &'static str {
+        match self {
+            ShortcutAction::LockScreen => "CmdOrCtrl+Alt+L",
+            ShortcutAction::TogglePresentation => "CmdOrCtrl+Alt+P",
+            ShortcutAction::MuteStudentScreens => "CmdOrCtrl+Alt+M",
+            ShortcutAction::QuickScreenshot => "CmdOrCtrl+Alt+S",
+        }
+    }
+
+    /// Whether a role may use the action; classroom controls are for staff
+    pub fn allowed_for(self, role: &UserRoleType) -> bool {
+        match self {
+            ShortcutAction::LockScreen | ShortcutAction::QuickScreenshot => true,
+            ShortcutAction::TogglePresentation | ShortcutAction::MuteStudentScreens => {
+                !matches!(role, UserRoleType::Student)
+            }
+        }
+    }
+
+    pub fn dispatch(self) -> ShortcutDispatch {
+        match self {
+            ShortcutAction::LockScreen | ShortcutAction::TogglePresentation => {
+                ShortcutDispatch::Backend
+            }
+            ShortcutAction::MuteStudentScreens | ShortcutAction::QuickScreenshot => {
+                ShortcutDispatch::Frontend
+            }
+        }
+    }
+}
+
+/// Why a binding is or is not registered
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BindingStatus {
+    Active,
+    /// Turned off with an empty override
+    Disabled,
+    /// The accelerator could not be parsed
+    Invalid,
+    /// Another action or a reserved key sequence uses the same keys
+    Conflict,
+    /// Not available to the signed-in user's role, or nobody is signed in
+    Unavailable,
+}
+
+/// An action and the keys bound to it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShortcutBinding {
+    pub action: ShortcutAction,
+    /// Accelerator as configured, e.g. "CmdOrCtrl+Alt+L"
+    pub accelerator: Option<String>,
+    /// Whether the accelerator comes from `shortcuts.overrides`
+    pub customized: bool,
+    pub dispatch: ShortcutDispatch,
+    pub status: BindingStatus,
+}
+
+/// Actions sharing the same keys
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ShortcutConflict {
+    pub accelerator: String,
+    pub actions: Vec<ShortcutAction>,
+    /// Reserved key sequence the keys collide with, e.g. "kiosk_escape"
+    pub reserved: Option<String>,
+}
+
+/// Shortcut settings for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShortcutOverview {
+    pub enabled: bool,
+    pub bindings: Vec<ShortcutBinding>,
+    pub conflicts: Vec<ShortcutConflict>,
+}
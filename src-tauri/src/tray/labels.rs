@@ -0,0 +1,61 @@
+//! Tray Labels
+//! Localized tray menu text for the languages the frontend ships
+
+/// Menu text in one language
+pub struct TrayLabels {
+    pub not_signed_in: &'static str,
+    pub status_active: &'static str,
+    pub status_locked: &'static str,
+    pub open_dashboard: &'static str,
+    pub lock: &'static str,
+    pub logout: &'static str,
+    pub language: &'static str,
+    pub theme: &'static str,
+    pub theme_light: &'static str,
+    pub theme_dark: &'static str,
+    pub theme_system: &'static str,
+    pub quit: &'static str,
+}
+
+const EN: TrayLabels = TrayLabels {
+    not_signed_in: "Not signed in",
+    status_active: "Active",
+    status_locked: "Locked",
+    open_dashboard: "Open Dashboard",
+    lock: "Lock",
+    logout: "Log Out",
+    language: "Language",
+    theme: "Theme",
+    theme_light: "Light",
+    theme_dark: "Dark",
+    theme_system: "System",
+    quit: "Quit",
+};
+
+const VI: TrayLabels = TrayLabels {
+    not_signed_in: "Chưa đăng nhập",
+    status_active: "Đang hoạt động",
+    status_locked: "Đã khóa",
+    open_dashboard: "Mở bảng điều khiển",
+    lock: "Khóa",
+    logout: "Đăng xuất",
+    language: "Ngôn ngữ",
+    theme: "Giao diện",
+    theme_light: "Sáng",
+    theme_dark: "Tối",
+    theme_system: "Theo hệ thống",
+    quit: "Thoát",
+};
+
+/// Languages offered in the tray, as (code, native name)
+pub const LANGUAGES: [(&str, &str); 2] = [("en", "English"), ("vi", "Tiếng Việt")];
+
+impl TrayLabels {
+    /// Labels for a language code, falling back to English
+    pub fn for_language(language: &str) -> &'static TrayLabels {
+        match language {
+            "vi" => &VI,
+            _ => &EN,
+        }
+    }
+}
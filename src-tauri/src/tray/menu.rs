@@ -0,0 +1,234 @@
+//! Tray Menu
+//! System tray icon whose menu reflects the signed-in user and lock state
+
+use tauri::menu::{CheckMenuItem, Menu, MenuEvent, MenuItem, PredefinedMenuItem, Submenu};
+use tauri::tray::TrayIconBuilder;
+use tauri::{AppHandle, Emitter, Manager};
+
+use super::labels::{TrayLabels, LANGUAGES};
+use crate::auth::SessionState;
+use crate::config::{load_config, save_config, Theme};
+use crate::idle::{lock_session, IdleMonitor, LockState};
+use crate::window::{kiosk_active, logout_to_auth, open_main_window};
+
+/// Tray icon id
+const TRAY_ID: &str = "main";
+
+/// Application name shown in the tooltip
+const APP_NAME: &str = "Smart School";
+
+/// Event emitted when the language is changed from the tray
+pub const LANGUAGE_CHANGED_EVENT: &str = "config://language-changed";
+
+/// Event emitted when the theme is changed from the tray
+pub const THEME_CHANGED_EVENT: &str = "config://theme-changed";
+
+const OPEN_DASHBOARD_ID: &str = "open_dashboard";
+const LOCK_ID: &str = "lock";
+const LOGOUT_ID: &str = "logout";
+const QUIT_ID: &str = "quit";
+const LANGUAGE_PREFIX: &str = "language:";
+const THEME_PREFIX: &str = "theme:";
+
+/// Create the tray icon
+pub fn create_tray(app: &AppHandle) -> tauri::Result<()> {
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .menu(&build_menu(app)?)
+        .tooltip(tooltip(app))
+        .show_menu_on_left_click(true)
+        .on_menu_event(handle_menu_event);
+
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+
+    builder.build(app)?;
+    Ok(())
+}
+
+/// Whether the tray icon was created (some desktops have no status area)
+pub fn tray_available(app: &AppHandle) -> bool {
+    app.tray_by_id(TRAY_ID).is_some()
+}
+
+/// Rebuild the tray menu after the session, lock state, language or theme changes
+pub fn refresh_tray(app: &AppHandle) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+
+    if let Ok(menu) = build_menu(app) {
+        let _ = tray.set_menu(Some(menu));
+    }
+    let _ = tray.set_tooltip(Some(tooltip(app)));
+}
+
+/// "Name — Status" for the signed-in user, or the not-signed-in label
+fn status_line(app: &AppHandle, labels: &TrayLabels) -> String {
+    match app.state::<SessionState>().current_user() {
+        Some(user) => {
+            let status = match app.state::<IdleMonitor>().state() {
+                LockState::Locked => labels.status_locked,
+                LockState::Unlocked => labels.status_active,
+            };
+            format!("{} — {}", user.full_name, status)
+        }
+        None => labels.not_signed_in.to_string(),
+    }
+}
+
+fn tooltip(app: &AppHandle) -> String {
+    let labels = TrayLabels::for_language(&load_config(app).language);
+    format!("{}\n{}", APP_NAME, status_line(app, labels))
+}
+
+fn build_menu(app: &AppHandle) -> tauri::Result<Menu<tauri::Wry>> {
+    let config = load_config(app);
+    let labels = TrayLabels::for_language(&config.language);
+    let signed_in = app.state::<SessionState>().is_active();
+    let locked = app.state::<IdleMonitor>().state() == LockState::Locked;
+
+    let status = MenuItem::new(app, status_line(app, labels), false, None::<&str>)?;
+    let open_dashboard = MenuItem::with_id(
+        app,
+        OPEN_DASHBOARD_ID,
+        labels.open_dashboard,
+        signed_in,
+        None::<&str>,
+    )?;
+    let lock = MenuItem::with_id(
+        app,
+        LOCK_ID,
+        labels.lock,
+        signed_in && !locked,
+        None::<&str>,
+    )?;
+    let logout = MenuItem::with_id(app, LOGOUT_ID, labels.logout, signed_in, None::<&str>)?;
+
+    let language_items = LANGUAGES
+        .iter()
+        .map(|(code, name)| {
+            CheckMenuItem::with_id(
+                app,
+                format!("{}{}", LANGUAGE_PREFIX, code),
+                *name,
+                true,
+                config.language == *code,
+                None::<&str>,
+            )
+        })
+        .collect::<tauri::Result<Vec<_>>>()?;
+    let language = Submenu::with_items(
+        app,
+        labels.language,
+        true,
+        &language_items
+            .iter()
+            .map(|item| item as &dyn tauri::menu::IsMenuItem<tauri::Wry>)
+            .collect::<Vec<_>>(),
+    )?;
+
+    let themes = [
+        (Theme::Light, labels.theme_light),
+        (Theme::Dark, labels.theme_dark),
+        (Theme::System, labels.theme_system),
+    ];
+    let theme_items = themes
+        .iter()
+        .map(|(theme, name)| {
+            CheckMenuItem::with_id(
+                app,
+                format!("{}{}", THEME_PREFIX, theme.as_str()),
+                *name,
+                true,
+                config.theme == *theme,
+                None::<&str>,
+            )
+        })
+        .collect::<tauri::Result<Vec<_>>>()?;
+    let theme = Submenu::with_items(
+        app,
+        labels.theme,
+        true,
+        &theme_items
+            .iter()
+            .map(|item| item as &dyn tauri::menu::IsMenuItem<tauri::Wry>)
+            .collect::<Vec<_>>(),
+    )?;
+
+    // Quitting from the tray would bypass kiosk mode
+    let quit = MenuItem::with_id(app, QUIT_ID, labels.quit, !kiosk_active(app), None::<&str>)?;
+
+    Menu::with_items(
+        app,
+        &[
+            &status,
+            &PredefinedMenuItem::separator(app)?,
+            &open_dashboard,
+            &lock,
+            &logout,
+            &PredefinedMenuItem::separator(app)?,
+            &language,
+            &theme,
+            &PredefinedMenuItem::separator(app)?,
+            &quit,
+        ],
+    )
+}
+
+fn handle_menu_event(app: &AppHandle, event: MenuEvent) {
+    let id = event.id.as_ref();
+
+    if let Some(language) = id.strip_prefix(LANGUAGE_PREFIX) {
+        set_language(app, language);
+        return;
+    }
+    if let Some(theme) = id.strip_prefix(THEME_PREFIX) {
+        set_theme(app, theme);
+        return;
+    }
+
+    match id {
+        OPEN_DASHBOARD_ID => {
+            let app = app.clone();
+            tauri::async_runtime::spawn(async move {
+                let _ = open_main_window(app).await;
+            });
+        }
+        LOCK_ID => lock_session(app.clone()),
+        LOGOUT_ID => logout(app),
+        QUIT_ID => app.exit(0),
+        _ => {}
+    }
+}
+
+fn set_language(app: &AppHandle, language: &str) {
+    let mut config = load_config(app);
+    config.language = language.to_string();
+    if save_config(app, &config).is_ok() {
+        let _ = app.emit(LANGUAGE_CHANGED_EVENT, language);
+    }
+    refresh_tray(app);
+}
+
+fn set_theme(app: &AppHandle, theme: &str) {
+    let Some(theme) = Theme::from_str(theme) else {
+        return;
+    };
+    let name = theme.as_str();
+
+    let mut config = load_config(app);
+    config.theme = theme;
+    if save_config(app, &config).is_ok() {
+        let _ = app.emit(THEME_CHANGED_EVENT, name);
+    }
+    refresh_tray(app);
+}
+
+/// End the session and return to the login window
+fn logout(app: &AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let _ = logout_to_auth(app).await;
+    });
+}
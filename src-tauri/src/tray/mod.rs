@@ -0,0 +1,11 @@
+//! Tray Module
+//! System tray icon with a localized, session-aware menu
+
+#![allow(dead_code)]
+#![allow(unused_imports)]
+
+mod labels;
+mod menu;
+
+pub use labels::*;
+pub use menu::*;
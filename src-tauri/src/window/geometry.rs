@@ -0,0 +1,202 @@
+//! Window Geometry
+//! Applies saved window size, position and screen mode, clamped to the connected monitors
+
+use tauri::{PhysicalPosition, PhysicalSize, WebviewWindow};
+
+use crate::config::{ScreenMode, WindowConfig};
+
+/// Minimum part of a window (in physical pixels) that must be on a monitor
+/// for its saved position to be reused
+const MIN_VISIBLE: i64 = 100;
+
+/// Usable area of a monitor in physical pixels
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MonitorArea {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Where and how large a window should open
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Placement {
+    pub width: u32,
+    pub height: u32,
+    /// `None` means center on the current monitor
+    pub position: Option<(i32, i32)>,
+}
+
+/// Fit a saved geometry onto the connected monitors
+///
+/// The saved position is kept when enough of the window lands on a monitor,
+/// shifted so the whole window fits on it; otherwise the window is centered.
+/// Sizes are shrunk to the largest monitor. With no monitor information the
+/// saved values are used as-is.
+pub fn place_window(config: &WindowConfig, monitors: &[MonitorArea]) -> Placement {
+    let saved_position = config.x.zip(config.y);
+
+    let Some(largest) = monitors
+        .iter()
+        .max_by_key(|m| u64::from(m.width) * u64::from(m.height))
+    else {
+        return Placement {
+            width: config.width,
+            height: config.height,
+            position: saved_position,
+        };
+    };
+
+    let width = config.width.clamp(1, largest.width);
+    let height = config.height.clamp(1, largest.height);
+
+    let position = saved_position.and_then(|(x, y)| {
+        let (monitor, visible) = monitors
+            .iter()
+            .map(|m| (m, overlap(m, x, y, width, height)))
+            .max_by_key(|(_, (w, h))| w * h)?;
+        if visible.0 < MIN_VISIBLE.min(i64::from(width))
+            || visible.1 < MIN_VISIBLE.min(i64::from(height))
+        {
+            return None;
+        }
+
+        // Fits on the monitor it mostly covers, or falls back to centering
+        if width > monitor.width || height > monitor.height {
+            return None;
+        }
+        let max_x = monitor.x + (monitor.width - width) as i32;
+        let max_y = monitor.y + (monitor.height - height) as i32;
+        Some((x.clamp(monitor.x, max_x), y.clamp(monitor.y, max_y)))
+    });
+
+    Placement {
+        width,
+        height,
+        position,
+    }
+}
+
+/// Width and height of the intersection between a window and a monitor
+fn overlap(monitor: &MonitorArea, x: i32, y: i32, width: u32, height: u32) -> (i64, i64) {
+    let span = |start: i32, len: u32, area_start: i32, area_len: u32| {
+        let lo = i64::from(start).max(i64::from(area_start));
+        let hi =
+            (i64::from(start) + i64::from(len)).min(i64::from(area_start) + i64::from(area_len));
+        (hi - lo).max(0)
+    };
+    (
+        span(x, width, monitor.x, monitor.width),
+        span(y, height, monitor.y, monitor.height),
+    )
+}
+
+/// Work areas of the monitors currently connected
+pub fn monitor_areas(window: &WebviewWindow) -> Vec<MonitorArea> {
+    window
+        .available_monitors()
+        .unwrap_or_default()
+        .iter()
+        .map(|monitor| {
+            let area = monitor.work_area();
+            MonitorArea {
+                x: area.position.x,
+                y: area.position.y,
+                width: area.size.width,
+                height: area.size.height,
+            }
+        })
+        .collect()
+}
+
+/// Apply a saved geometry to a window, leaving any fullscreen or maximized state first
+pub fn apply_window_config(window: &WebviewWindow, config: &WindowConfig) -> tauri::Result<()> {
+    let placement = place_window(config, &monitor_areas(window));
+
+    if window.is_fullscreen()? {
+        window.set_fullscreen(false)?;
+    }
+    if window.is_maximized()? {
+        window.unmaximize()?;
+    }
+
+    window.set_size(PhysicalSize::new(placement.width, placement.height))?;
+    match placement.position {
+        Some((x, y)) => window.set_position(PhysicalPosition::new(x, y))?,
+        None => window.center()?,
+    }
+
+    match config.screen_mode {
+        ScreenMode::Maximized => window.maximize()?,
+        ScreenMode::Fullscreen => window.set_fullscreen(true)?,
+        ScreenMode::Normal => {}
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRIMARY: MonitorArea = MonitorArea {
+        x: 0,
+        y: 0,
+        width: 1920,
+        height: 1040,
+    };
+    const SECONDARY: MonitorArea = MonitorArea {
+        x: 1920,
+        y: 0,
+        width: 1280,
+        height: 1024,
+    };
+
+    fn config(width: u32, height: u32, x: Option<i32>, y: Option<i32>) -> WindowConfig {
+        WindowConfig {
+            width,
+            height,
+            x,
+            y,
+            screen_mode: ScreenMode::Normal,
+        }
+    }
+
+    #[test]
+    fn test_keeps_position_on_connected_monitor() {
+        let placement = place_window(
+            &config(1200, 800, Some(2000), Some(100)),
+            &[PRIMARY, SECONDARY],
+        );
+        assert_eq!(placement.position, Some((2000, 100)));
+    }
+
+    #[test]
+    fn test_centers_when_monitor_is_disconnected() {
+        // Saved on the secondary monitor, which is no longer attached
+        let placement = place_window(&config(1200, 800, Some(2000), Some(100)), &[PRIMARY]);
+        assert_eq!(placement.position, None);
+    }
+
+    #[test]
+    fn test_clamps_size_and_partially_visible_position() {
+        let placement = place_window(&config(2500, 1400, Some(-50), Some(-20)), &[PRIMARY]);
+        assert_eq!((placement.width, placement.height), (1920, 1040));
+        assert_eq!(placement.position, Some((0, 0)));
+
+        let placement = place_window(&config(1200, 800, Some(1500), Some(600)), &[PRIMARY]);
+        assert_eq!(placement.position, Some((720, 240)));
+    }
+
+    #[test]
+    fn test_uses_saved_values_without_monitor_info() {
+        let saved = config(1200, 800, Some(5000), Some(5000));
+        assert_eq!(
+            place_window(&saved, &[]),
+            Placement {
+                width: 1200,
+                height: 800,
+                position: Some((5000, 5000)),
+            }
+        );
+    }
+}
@@ -0,0 +1,97 @@
+//! Instance Handling
+//! Single-instance forwarding and close-to-tray for the main window
+
+use serde::{Deserialize, Serialize};
+use tauri::{Emitter, Manager, Window, WindowEvent};
+
+use super::kiosk::kiosk_active;
+use super::registry::forget_window;
+use super::state::track_window_geometry;
+use crate::auth::SessionState;
+use crate::config::load_config;
+use crate::deep_link::{handle_deep_links, is_deep_link};
+use crate::tray::tray_available;
+
+/// Event emitted when another launch was redirected to this instance
+pub const SECOND_INSTANCE_EVENT: &str = "app://second-instance";
+
+/// Arguments forwarded from a second launch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecondInstancePayload {
+    pub args: Vec<String>,
+    pub cwd: String,
+    /// `smartschool://` links found in the arguments, already routed by the backend
+    pub deep_links: Vec<String>,
+}
+
+/// Called by the single-instance plugin when the app is launched again
+pub fn handle_second_instance(app: &tauri::AppHandle, args: Vec<String>, cwd: String) {
+    let deep_links = args
+        .iter()
+        .filter(|arg| is_deep_link(arg))
+        .cloned()
+        .collect::<Vec<_>>();
+
+    focus_active_window(app);
+    handle_deep_links(app, &deep_links);
+    let _ = app.emit(
+        SECOND_INSTANCE_EVENT,
+        SecondInstancePayload {
+            args,
+            cwd,
+            deep_links,
+        },
+    );
+}
+
+/// Bring the window the user is working in to the front
+///
+/// That is `main` while someone is signed in, otherwise the login window.
+pub fn focus_active_window(app: &tauri::AppHandle) {
+    let label = if app.state::<SessionState>().is_active() {
+        "main"
+    } else {
+        "auth"
+    };
+
+    let window = app
+        .get_webview_window(label)
+        .or_else(|| app.get_webview_window("main"))
+        .or_else(|| app.get_webview_window("auth"));
+
+    if let Some(window) = window {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+/// Track geometry changes and destroyed windows, and handle close requests:
+/// ignored for every window in kiosk mode, and `main` is hidden to the tray
+/// when `startup.close_to_tray` is enabled
+pub fn handle_window_event(window: &Window, event: &WindowEvent) {
+    track_window_geometry(window, event);
+    if let WindowEvent::Destroyed = event {
+        forget_window(window.app_handle(), window.label());
+    }
+
+    let WindowEvent::CloseRequested { api, .. } = event else {
+        return;
+    };
+    let app = window.app_handle();
+
+    // Alt+F4 and other OS close requests are ignored in kiosk mode
+    if kiosk_active(app) {
+        api.prevent_close();
+        return;
+    }
+
+    if window.label() != "main" {
+        return;
+    }
+
+    if load_config(app).startup.close_to_tray && tray_available(app) {
+        api.prevent_close();
+        let _ = window.hide();
+    }
+}
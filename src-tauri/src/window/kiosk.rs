@@ -0,0 +1,203 @@
+//! Kiosk Mode
+//! Locked-down main window for student lab machines, with an admin escape
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{Emitter, Manager, WebviewWindow};
+
+use super::state::restore_geometry;
+use crate::audit::{record_audit_event, AuditEvent, AuditEventKind};
+use crate::auth::{
+    AuthError, AuthErrorKind, LoginThrottleState, SecretHash, SessionState, UnlockMethod,
+};
+use crate::common::{app_data_file, read_yaml, write_yaml, AppError, AppResult, ErrorCode};
+use crate::config::load_config;
+use crate::shortcuts::refresh_shortcuts;
+use crate::tray::refresh_tray;
+
+/// Kiosk secrets file name
+const KIOSK_FILE_NAME: &str = "kiosk.yaml";
+
+/// Throttle key for exit attempts, shared with the login throttle
+const EXIT_THROTTLE_KEY: &str = "kiosk:exit";
+
+/// Event emitted when an admin leaves kiosk mode
+pub const KIOSK_EXITED_EVENT: &str = "kiosk://exited";
+
+/// Event emitted when kiosk mode is enforced again
+pub const KIOSK_RESUMED_EVENT: &str = "kiosk://resumed";
+
+/// Event sent to the main window when the escape sequence is pressed,
+/// asking it to show the exit prompt
+pub const KIOSK_EXIT_REQUESTED_EVENT: &str = "kiosk://exit-requested";
+
+/// Locally stored kiosk secrets
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct KioskSecrets {
+    /// Dedicated exit password set by an admin
+    pub exit_password: Option<SecretHash>,
+}
+
+/// Kiosk state for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KioskStatus {
+    pub enabled: bool,
+    /// Enabled and not suspended by an admin
+    pub active: bool,
+    pub escape_sequence: String,
+    pub exit_password_set: bool,
+}
+
+/// Managed state tracking whether an admin suspended kiosk mode for this run
+#[derive(Default)]
+pub struct KioskState {
+    suspended: AtomicBool,
+}
+
+/// Whether kiosk restrictions currently apply
+pub fn kiosk_active(app: &tauri::AppHandle) -> bool {
+    load_config(app).kiosk.enabled && !app.state::<KioskState>().suspended.load(Ordering::SeqCst)
+}
+
+/// Fail if a window control is disabled by kiosk mode
+///
+/// Every window is guarded, since closing the sign-in window would leave
+/// kiosk mode just as well as closing `main`.
+pub fn ensure_window_control_allowed(app: &tauri::AppHandle) -> AppResult<()> {
+    if kiosk_active(app) {
+        return Err(
+            AppError::permission("This action is disabled in kiosk mode")
+                .with_code(ErrorCode::KioskActionDisabled),
+        );
+    }
+    Ok(())
+}
+
+/// Apply or lift kiosk restrictions on a window
+pub fn apply_kiosk(window: &WebviewWindow, active: bool) -> tauri::Result<()> {
+    window.set_fullscreen(active)?;
+    window.set_always_on_top(active)?;
+    window.set_resizable(!active)?;
+    window.set_minimizable(!active)?;
+    window.set_closable(!active)?;
+
+    if load_config(window.app_handle()).kiosk.hide_taskbar {
+        window.set_skip_taskbar(active)?;
+    }
+    Ok(())
+}
+
+/// Load kiosk secrets from disk
+pub fn load_kiosk_secrets(app: &tauri::AppHandle) -> AppResult<KioskSecrets> {
+    let path = app_data_file(app, KIOSK_FILE_NAME)?;
+    Ok(read_yaml(&path)?.unwrap_or_default())
+}
+
+/// Save kiosk secrets to disk
+pub fn save_kiosk_secrets(app: &tauri::AppHandle, secrets: &KioskSecrets) -> AppResult<()> {
+    let path = app_data_file(app, KIOSK_FILE_NAME)?;
+    write_yaml(&path, secrets)
+}
+
+/// Get whether kiosk mode is enabled and active
+#[tauri::command]
+pub fn get_kiosk_status(app: tauri::AppHandle) -> AppResult<KioskStatus> {
+    let kiosk = load_config(&app).kiosk;
+    Ok(KioskStatus {
+        enabled: kiosk.enabled,
+        active: kiosk_active(&app),
+        escape_sequence: kiosk.escape_sequence,
+        exit_password_set: load_kiosk_secrets(&app)?.exit_password.is_some(),
+    })
+}
+
+/// Set the kiosk exit password (admin only)
+#[tauri::command]
+pub fn set_kiosk_exit_password(
+    app: tauri::AppHandle,
+    session: tauri::State<'_, SessionState>,
+    password: String,
+) -> AppResult<()> {
+    session.require_admin()?;
+    if password.is_empty() {
+        return Err(AuthError::new(
+            AuthErrorKind::InvalidCredentials,
+            "Exit password cannot be empty",
+        )
+        .into());
+    }
+
+    let mut secrets = load_kiosk_secrets(&app)?;
+    secrets.exit_password = Some(SecretHash::new(&password));
+    save_kiosk_secrets(&app, &secrets)?;
+    Ok(())
+}
+
+/// Leave kiosk mode until the next start or `resume_kiosk`
+/// Requires the exit password, or a signed-in admin's password when none is set
+#[tauri::command]
+pub fn exit_kiosk(
+    app: tauri::AppHandle,
+    kiosk: tauri::State<'_, KioskState>,
+    session: tauri::State<'_, SessionState>,
+    password: String,
+) -> AppResult<()> {
+    if !kiosk_active(&app) {
+        return Ok(());
+    }
+
+    let throttle = app.state::<LoginThrottleState>();
+    throttle.check(&app, EXIT_THROTTLE_KEY)?;
+
+    let verified = match load_kiosk_secrets(&app)?.exit_password {
+        Some(hash) => hash.verify(&password),
+        None => {
+            session.require_admin().is_ok()
+                && session
+                    .verify_unlock(UnlockMethod::Password, &password)
+                    .is_ok()
+        }
+    };
+    throttle.record(&app, EXIT_THROTTLE_KEY, verified)?;
+
+    if !verified {
+        return Err(AuthError::new(
+            AuthErrorKind::InvalidCredentials,
+            "Incorrect kiosk exit password",
+        )
+        .into());
+    }
+
+    kiosk.suspended.store(true, Ordering::SeqCst);
+    if let Some(main_window) = app.get_webview_window("main") {
+        apply_kiosk(&main_window, false)?;
+        restore_geometry(&main_window)?;
+    }
+
+    record_audit_event(
+        &app,
+        AuditEvent::new(AuditEventKind::KioskExit)
+            .with_optional_user(session.current_user().as_ref()),
+    );
+    let _ = app.emit(KIOSK_EXITED_EVENT, ());
+    refresh_tray(&app);
+    refresh_shortcuts(&app);
+    Ok(())
+}
+
+/// Enforce kiosk mode again after an admin exit
+#[tauri::command]
+pub fn resume_kiosk(app: tauri::AppHandle, kiosk: tauri::State<'_, KioskState>) -> AppResult<()> {
+    kiosk.suspended.store(false, Ordering::SeqCst);
+    if !kiosk_active(&app) {
+        return Ok(());
+    }
+
+    if let Some(main_window) = app.get_webview_window("main") {
+        apply_kiosk(&main_window, true)?;
+    }
+    let _ = app.emit(KIOSK_RESUMED_EVENT, ());
+    refresh_tray(&app);
+    refresh_shortcuts(&app);
+    Ok(())
+}
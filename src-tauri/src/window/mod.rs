@@ -1,84 +1,64 @@
-use tauri::Manager;
+mod geometry;
+mod instance;
+mod kiosk;
+mod presentation;
+mod registry;
+mod state;
+mod transition;
 
-/// Opens the main dashboard window and closes the login window
-#[tauri::command]
-pub async fn open_main_window(app: tauri::AppHandle) -> Result<(), String> {
-    // Try to get existing main window, or create a new one
-    let main_window = match app.get_webview_window("main") {
-        Some(window) => window,
-        None => {
-            // Create new main window if it doesn't exist
-            tauri::WebviewWindowBuilder::new(
-                &app,
-                "main",
-                tauri::WebviewUrl::App("/#/dashboard".into()),
-            )
-            .title("Smart School")
-            .inner_size(1200.0, 800.0)
-            .min_inner_size(800.0, 600.0)
-            .resizable(true)
-            .center()
-            .decorations(false)
-            .visible(false)
-            .build()
-            .map_err(|e| e.to_string())?
-        }
-    };
+pub use instance::*;
+pub use kiosk::*;
+pub use presentation::*;
+pub use registry::*;
+pub use state::*;
+pub use transition::*;
 
-    // Show and focus main window
-    main_window.show().map_err(|e| e.to_string())?;
-    main_window.set_focus().map_err(|e| e.to_string())?;
+use tauri::{Emitter, Manager};
 
-    // Close auth window
-    if let Some(auth_window) = app.get_webview_window("auth") {
-        auth_window.close().map_err(|e| e.to_string())?;
-    }
+use crate::audit::AuditEventKind;
+use crate::auth::{end_session, LOGOUT_EVENT};
+use crate::common::AppResult;
 
-    Ok(())
+/// Opens the main dashboard window and closes the login window
+#[tauri::command]
+pub async fn open_main_window(app: tauri::AppHandle) -> AppResult<()> {
+    transition_to(&app, WindowStage::Main)
 }
 
 /// Logs out from dashboard and returns to auth window
 #[tauri::command]
-pub async fn logout_to_auth(app: tauri::AppHandle) -> Result<(), String> {
-    // Create and show new auth window
-    let auth_window = tauri::WebviewWindowBuilder::new(
-        &app,
-        "auth",
-        tauri::WebviewUrl::App("/".into()),
-    )
-    .title("Smart School - Auth")
-    .inner_size(450.0, 600.0)
-    .resizable(false)
-    .center()
-    .decorations(false)
-    .build()
-    .map_err(|e| e.to_string())?;
+pub async fn logout_to_auth(app: tauri::AppHandle) -> AppResult<()> {
+    sign_out(&app, AuditEventKind::Logout).await
+}
 
-    auth_window.show().map_err(|e| e.to_string())?;
-    auth_window.set_focus().map_err(|e| e.to_string())?;
+/// Ends the session, closes every other window, then shows the login window
+pub async fn sign_out(app: &tauri::AppHandle, kind: AuditEventKind) -> AppResult<()> {
+    end_session(app, kind).await;
 
-    // Close main window
-    if let Some(main_window) = app.get_webview_window("main") {
-        main_window.close().map_err(|e| e.to_string())?;
-    }
+    app.state::<PresentationState>().reset();
+    close_secondary_windows(app);
+    transition_to(app, WindowStage::Auth)?;
 
+    let _ = app.emit(LOGOUT_EVENT, ());
     Ok(())
 }
 
 /// Close a specific window by label
 #[tauri::command]
-pub async fn close_window(app: tauri::AppHandle, label: String) -> Result<(), String> {
+pub async fn close_window(app: tauri::AppHandle, label: String) -> AppResult<()> {
+    ensure_window_control_allowed(&app)?;
     if let Some(window) = app.get_webview_window(&label) {
-        window.close().map_err(|e| e.to_string())?;
+        window.close()?;
     }
     Ok(())
 }
 
 /// Minimize a specific window by label
 #[tauri::command]
-pub async fn minimize_window(app: tauri::AppHandle, label: String) -> Result<(), String> {
+pub async fn minimize_window(app: tauri::AppHandle, label: String) -> AppResult<()> {
+    ensure_window_control_allowed(&app)?;
     if let Some(window) = app.get_webview_window(&label) {
-        window.minimize().map_err(|e| e.to_string())?;
+        window.minimize()?;
     }
     Ok(())
 }
@@ -0,0 +1,303 @@
+//! Presentation Window
+//! Fullscreen window on a classroom display, controlled from the teacher's main window
+
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::{Emitter, Manager, PhysicalPosition};
+
+use super::registry::{create_window, validate_route, WindowKind, WindowParams};
+use crate::common::{AppError, AppResult, ErrorCode};
+
+/// Presentation window label
+pub const PRESENTATION_LABEL: &str = "presentation";
+
+/// Event sent to the presentation window with the route to show
+pub const PRESENTATION_NAVIGATE_EVENT: &str = "presentation://navigate";
+
+/// Route shown when nothing is being presented
+const BLANK_ROUTE: &str = "/presentation/blank";
+
+/// A connected display
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MonitorInfo {
+    /// Position in the list returned by `list_monitors`
+    pub index: usize,
+    pub name: Option<String>,
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub scale_factor: f64,
+    pub is_primary: bool,
+}
+
+/// What the presentation window shows
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(tag = "type", content = "route", rename_all = "lowercase")]
+pub enum PresentationContent {
+    #[default]
+    Blank,
+    /// Follow the route of the main window
+    Mirror,
+    /// Show a specific route
+    Route(String),
+}
+
+/// Presentation window state for the frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PresentationStatus {
+    pub open: bool,
+    pub monitor: Option<MonitorInfo>,
+    pub content: PresentationContent,
+}
+
+/// Managed state for the presentation window
+#[derive(Default)]
+pub struct PresentationState {
+    content: Mutex<PresentationContent>,
+    /// Last route reported by the main window, used when mirroring
+    main_route: Mutex<Option<String>>,
+}
+
+impl PresentationState {
+    /// Forget what was presented, e.g. on logout
+    pub fn reset(&self) {
+        *self.content.lock().unwrap() = PresentationContent::Blank;
+        *self.main_route.lock().unwrap() = None;
+    }
+
+    /// Route the presentation window should display
+    fn current_route(&self) -> String {
+        match &*self.content.lock().unwrap() {
+            PresentationContent::Blank => BLANK_ROUTE.to_string(),
+            PresentationContent::Mirror => self
+                .main_route
+                .lock()
+                .unwrap()
+                .clone()
+                .unwrap_or_else(|| BLANK_ROUTE.to_string()),
+            PresentationContent::Route(route) => route.clone(),
+        }
+    }
+}
+
+/// Requested monitor, or the first secondary display, or the only one
+fn pick_monitor(monitors: &[MonitorInfo], requested: Option<usize>) -> Option<&MonitorInfo> {
+    match requested {
+        Some(index) => monitors.get(index),
+        None => monitors
+            .iter()
+            .find(|m| !m.is_primary)
+            .or_else(|| monitors.first()),
+    }
+}
+
+/// Connected displays, in the order used for monitor indexes
+pub fn monitors(app: &tauri::AppHandle) -> Vec<MonitorInfo> {
+    let primary = app.primary_monitor().ok().flatten();
+
+    app.available_monitors()
+        .unwrap_or_default()
+        .iter()
+        .enumerate()
+        .map(|(index, monitor)| MonitorInfo {
+            index,
+            name: monitor.name().cloned(),
+            x: monitor.position().x,
+            y: monitor.position().y,
+            width: monitor.size().width,
+            height: monitor.size().height,
+            scale_factor: monitor.scale_factor(),
+            is_primary: primary
+                .as_ref()
+                .is_some_and(|p| p.position() == monitor.position() && p.size() == monitor.size()),
+        })
+        .collect()
+}
+
+/// Monitor containing a window's top-left corner
+fn window_monitor(app: &tauri::AppHandle, window: &tauri::WebviewWindow) -> Option<MonitorInfo> {
+    let position = window.outer_position().ok()?;
+    monitors(app).into_iter().find(|m| {
+        position.x >= m.x
+            && position.x < m.x + m.width as i32
+            && position.y >= m.y
+            && position.y < m.y + m.height as i32
+    })
+}
+
+fn display_not_connected(index: usize) -> AppError {
+    AppError::not_found(format!("Display {} is not connected", index))
+        .with_code(ErrorCode::DisplayNotConnected)
+        .with_param("display", index)
+}
+
+/// Show the current content in the presentation window
+fn push_route(app: &tauri::AppHandle) {
+    let route = app.state::<PresentationState>().current_route();
+    let _ = app.emit_to(PRESENTATION_LABEL, PRESENTATION_NAVIGATE_EVENT, route);
+}
+
+/// Move a window onto a monitor and make it fullscreen there
+fn place_on_monitor(window: &tauri::WebviewWindow, monitor: &MonitorInfo) -> tauri::Result<()> {
+    window.set_fullscreen(false)?;
+    window.set_position(PhysicalPosition::new(monitor.x, monitor.y))?;
+    window.set_fullscreen(true)
+}
+
+/// Close the presentation window if open, otherwise mirror main on the default display
+pub async fn toggle_presentation_window(app: &tauri::AppHandle) -> AppResult<()> {
+    match app.get_webview_window(PRESENTATION_LABEL) {
+        Some(window) => {
+            app.state::<PresentationState>().reset();
+            Ok(window.destroy()?)
+        }
+        None => {
+            open_presentation_window(app.clone(), None, Some(PresentationContent::Mirror)).await
+        }
+    }
+}
+
+/// List connected displays
+#[tauri::command]
+pub fn list_monitors(app: tauri::AppHandle) -> Vec<MonitorInfo> {
+    monitors(&app)
+}
+
+/// Open the presentation window fullscreen on a display
+/// Defaults to the first secondary display; an open window is moved instead
+#[tauri::command]
+pub async fn open_presentation_window(
+    app: tauri::AppHandle,
+    monitor: Option<usize>,
+    content: Option<PresentationContent>,
+) -> AppResult<()> {
+    let available = monitors(&app);
+    let target = pick_monitor(&available, monitor)
+        .cloned()
+        .ok_or_else(|| match monitor {
+            Some(index) => display_not_connected(index),
+            None => AppError::not_found("No display available for presentation")
+                .with_code(ErrorCode::NoDisplayAvailable),
+        })?;
+
+    if let Some(PresentationContent::Route(route)) = &content {
+        validate_route(route)?;
+    }
+    let state = app.state::<PresentationState>();
+    if let Some(content) = content {
+        *state.content.lock().unwrap() = content;
+    }
+
+    let params = WindowParams {
+        route: Some(state.current_route()),
+        ..WindowParams::default()
+    };
+    let window = create_window(&app, WindowKind::Presentation, &params)?;
+
+    place_on_monitor(&window, &target)?;
+    window.show()?;
+    push_route(&app);
+    Ok(())
+}
+
+/// Move the presentation window to another display
+#[tauri::command]
+pub fn move_presentation_window(app: tauri::AppHandle, monitor: usize) -> AppResult<()> {
+    let window = app.get_webview_window(PRESENTATION_LABEL).ok_or_else(|| {
+        AppError::not_found("Presentation window is not open")
+            .with_code(ErrorCode::PresentationNotOpen)
+    })?;
+    let available = monitors(&app);
+    let target =
+        pick_monitor(&available, Some(monitor)).ok_or_else(|| display_not_connected(monitor))?;
+
+    Ok(place_on_monitor(&window, target)?)
+}
+
+/// Choose what the presentation window shows: blank, a mirror of main, or a route
+#[tauri::command]
+pub fn set_presentation_content(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, PresentationState>,
+    content: PresentationContent,
+) -> AppResult<()> {
+    if let PresentationContent::Route(route) = &content {
+        validate_route(route)?;
+    }
+    *state.content.lock().unwrap() = content;
+    push_route(&app);
+    Ok(())
+}
+
+/// Report the main window's route so mirroring can follow it
+#[tauri::command]
+pub fn report_main_route(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, PresentationState>,
+    route: String,
+) -> AppResult<()> {
+    validate_route(&route)?;
+    *state.main_route.lock().unwrap() = Some(route);
+
+    if *state.content.lock().unwrap() == PresentationContent::Mirror {
+        push_route(&app);
+    }
+    Ok(())
+}
+
+/// Close the presentation window
+#[tauri::command]
+pub fn close_presentation_window(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, PresentationState>,
+) -> AppResult<()> {
+    *state.content.lock().unwrap() = PresentationContent::Blank;
+    if let Some(window) = app.get_webview_window(PRESENTATION_LABEL) {
+        window.destroy()?;
+    }
+    Ok(())
+}
+
+/// Get whether the presentation window is open, where, and what it shows
+#[tauri::command]
+pub fn get_presentation_status(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, PresentationState>,
+) -> PresentationStatus {
+    let window = app.get_webview_window(PRESENTATION_LABEL);
+    PresentationStatus {
+        open: window.is_some(),
+        monitor: window.and_then(|w| window_monitor(&app, &w)),
+        content: state.content.lock().unwrap().clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitor(index: usize, is_primary: bool) -> MonitorInfo {
+        MonitorInfo {
+            index,
+            name: None,
+            x: index as i32 * 1920,
+            y: 0,
+            width: 1920,
+            height: 1080,
+            scale_factor: 1.0,
+            is_primary,
+        }
+    }
+
+    #[test]
+    fn test_pick_monitor_prefers_secondary_display() {
+        let monitors = [monitor(0, true), monitor(1, false)];
+        assert_eq!(pick_monitor(&monitors, None).unwrap().index, 1);
+        assert_eq!(pick_monitor(&monitors, Some(0)).unwrap().index, 0);
+        assert!(pick_monitor(&monitors, Some(2)).is_none());
+
+        let single = [monitor(0, true)];
+        assert_eq!(pick_monitor(&single, None).unwrap().index, 0);
+    }
+}
@@ -0,0 +1,363 @@
+//! Window Registry
+//! Window kinds the app can open, and the windows currently open
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Mutex;
+use tauri::{Emitter, Manager, WebviewWindow};
+
+use super::kiosk::kiosk_active;
+use super::state::restore_geometry;
+use crate::common::{AppError, AppResult, ErrorCode};
+
+/// Event emitted when a window is created
+pub const WINDOW_OPENED_EVENT: &str = "window://opened";
+
+/// Event emitted when a window is destroyed
+pub const WINDOW_CLOSED_EVENT: &str = "window://closed";
+
+/// Kinds of windows the app can open
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum WindowKind {
+    Auth,
+    Main,
+    Presentation,
+    Settings,
+    Help,
+}
+
+/// How a window kind is built
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WindowSpec {
+    /// Label of a singleton, or the prefix of `<label>-<n>` labels
+    pub label: &'static str,
+    /// Hash route loaded when no route is requested
+    pub route: &'static str,
+    pub title: &'static str,
+    pub width: f64,
+    pub height: f64,
+    pub min_size: Option<(f64, f64)>,
+    pub resizable: bool,
+    pub decorations: bool,
+    /// Only one window of this kind may exist
+    pub singleton: bool,
+    /// Take focus when shown
+    pub focused: bool,
+    pub skip_taskbar: bool,
+}
+
+const fn spec(label: &'static str, route: &'static str, title: &'static str) -> WindowSpec {
+    WindowSpec {
+        label,
+        route,
+        title,
+        width: 800.0,
+        height: 600.0,
+        min_size: None,
+        resizable: true,
+        decorations: false,
+        singleton: true,
+        focused: true,
+        skip_taskbar: false,
+    }
+}
+
+impl WindowKind {
+    pub const ALL: [WindowKind; 5] = [
+        WindowKind::Auth,
+        WindowKind::Main,
+        WindowKind::Presentation,
+        WindowKind::Settings,
+        WindowKind::Help,
+    ];
+
+    pub fn spec(self) -> WindowSpec {
+        match self {
+            WindowKind::Auth => WindowSpec {
+                width: 450.0,
+                height: 600.0,
+                resizable: false,
+                ..spec("auth", "/", "Smart School - Auth")
+            },
+            WindowKind::Main => WindowSpec {
+                width: 1200.0,
+                height: 800.0,
+                min_size: Some((800.0, 600.0)),
+                ..spec("main", "/dashboard", "Smart School")
+            },
+            WindowKind::Presentation => WindowSpec {
+                focused: false,
+                skip_taskbar: true,
+                ..spec(
+                    "presentation",
+                    "/presentation/blank",
+                    "Smart School - Presentation",
+                )
+            },
+            WindowKind::Settings => WindowSpec {
+                width: 720.0,
+                height: 560.0,
+                min_size: Some((600.0, 480.0)),
+                ..spec("settings", "/settings", "Smart School - Settings")
+            },
+            WindowKind::Help => WindowSpec {
+                width: 640.0,
+                height: 720.0,
+                min_size: Some((420.0, 480.0)),
+                singleton: false,
+                ..spec("help", "/help", "Smart School - Help")
+            },
+        }
+    }
+
+    /// Kinds features may open with `open_window`; auth, main and the
+    /// presentation window have dedicated commands
+    pub fn detachable(self) -> bool {
+        matches!(self, WindowKind::Settings | WindowKind::Help)
+    }
+
+    /// Kind of a window from its label
+    pub fn from_label(label: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| {
+            let spec = kind.spec();
+            label == spec.label
+                || (!spec.singleton
+                    && label
+                        .strip_prefix(spec.label)
+                        .and_then(|rest| rest.strip_prefix('-'))
+                        .is_some_and(|n| n.parse::<u32>().is_ok()))
+        })
+    }
+}
+
+/// Options for opening a window
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WindowParams {
+    /// Route to load instead of the kind's default, e.g. "/help/nfc"
+    pub route: Option<String>,
+    pub title: Option<String>,
+}
+
+/// An open window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WindowInfo {
+    pub label: String,
+    pub kind: Option<WindowKind>,
+    /// Route the window was opened with, when opened through the registry
+    pub route: Option<String>,
+    pub opened_at: Option<DateTime<Utc>>,
+    pub visible: bool,
+    pub focused: bool,
+}
+
+#[derive(Debug, Clone)]
+struct WindowRecord {
+    kind: WindowKind,
+    route: String,
+    opened_at: DateTime<Utc>,
+}
+
+/// Managed state tracking windows opened through the registry
+#[derive(Default)]
+pub struct WindowRegistry {
+    records: Mutex<BTreeMap<String, WindowRecord>>,
+    next_instance: AtomicU32,
+}
+
+impl WindowRegistry {
+    /// Label for a new window of a kind
+    fn next_label(&self, spec: &WindowSpec) -> String {
+        if spec.singleton {
+            return spec.label.to_string();
+        }
+        let n = self.next_instance.fetch_add(1, Ordering::SeqCst) + 1;
+        format!("{}-{}", spec.label, n)
+    }
+}
+
+/// Only in-app routes may be loaded into a window
+pub fn validate_route(route: &str) -> AppResult<()> {
+    if route.starts_with('/') && !route.starts_with("//") && !route.contains("://") {
+        Ok(())
+    } else {
+        Err(
+            AppError::invalid_input(format!("Invalid window route: {}", route))
+                .with_code(ErrorCode::WindowInvalidRoute)
+                .with_param("route", route),
+        )
+    }
+}
+
+fn window_info(app: &tauri::AppHandle, window: &WebviewWindow) -> WindowInfo {
+    let record = app
+        .state::<WindowRegistry>()
+        .records
+        .lock()
+        .unwrap()
+        .get(window.label())
+        .cloned();
+
+    WindowInfo {
+        label: window.label().to_string(),
+        kind: record
+            .as_ref()
+            .map(|r| r.kind)
+            .or_else(|| WindowKind::from_label(window.label())),
+        route: record.as_ref().map(|r| r.route.clone()),
+        opened_at: record.map(|r| r.opened_at),
+        visible: window.is_visible().unwrap_or(false),
+        focused: window.is_focused().unwrap_or(false),
+    }
+}
+
+/// Build a hidden window of a kind and start tracking it
+///
+/// Singletons that already exist are returned as they are.
+pub fn create_window(
+    app: &tauri::AppHandle,
+    kind: WindowKind,
+    params: &WindowParams,
+) -> AppResult<WebviewWindow> {
+    let spec = kind.spec();
+    if spec.singleton {
+        if let Some(window) = app.get_webview_window(spec.label) {
+            return Ok(window);
+        }
+    }
+
+    let route = params.route.as_deref().unwrap_or(spec.route);
+    validate_route(route)?;
+
+    let registry = app.state::<WindowRegistry>();
+    let label = registry.next_label(&spec);
+    let mut builder = tauri::WebviewWindowBuilder::new(
+        app,
+        &label,
+        tauri::WebviewUrl::App(format!("/#{}", route).into()),
+    )
+    .title(params.title.as_deref().unwrap_or(spec.title))
+    .inner_size(spec.width, spec.height)
+    .resizable(spec.resizable)
+    .decorations(spec.decorations)
+    .focused(spec.focused)
+    .skip_taskbar(spec.skip_taskbar)
+    .center()
+    .visible(false);
+    if let Some((width, height)) = spec.min_size {
+        builder = builder.min_inner_size(width, height);
+    }
+    let window = builder.build()?;
+
+    registry.records.lock().unwrap().insert(
+        label,
+        WindowRecord {
+            kind,
+            route: route.to_string(),
+            opened_at: Utc::now(),
+        },
+    );
+    let _ = app.emit(WINDOW_OPENED_EVENT, window_info(app, &window));
+    Ok(window)
+}
+
+/// Stop tracking a destroyed window
+pub fn forget_window(app: &tauri::AppHandle, label: &str) {
+    app.state::<WindowRegistry>()
+        .records
+        .lock()
+        .unwrap()
+        .remove(label);
+    let _ = app.emit(WINDOW_CLOSED_EVENT, label);
+}
+
+/// Destroy every window other than the login and main windows
+pub fn close_secondary_windows(app: &tauri::AppHandle) {
+    let primary = [WindowKind::Auth.spec().label, WindowKind::Main.spec().label];
+    for (label, window) in app.webview_windows() {
+        if !primary.contains(&label.as_str()) {
+            let _ = window.destroy();
+        }
+    }
+}
+
+/// Open a detachable window (settings, help) and return its label
+/// An open singleton is focused instead
+#[tauri::command]
+pub async fn open_window(
+    app: tauri::AppHandle,
+    kind: WindowKind,
+    params: Option<WindowParams>,
+) -> AppResult<String> {
+    if !kind.detachable() {
+        return Err(AppError::invalid_input(format!(
+            "{:?} windows cannot be opened with open_window",
+            kind
+        ))
+        .with_code(ErrorCode::WindowNotDetachable)
+        .with_param("kind", format!("{:?}", kind)));
+    }
+    if kiosk_active(&app) {
+        return Err(
+            AppError::permission("Pop-out windows are disabled in kiosk mode")
+                .with_code(ErrorCode::KioskActionDisabled),
+        );
+    }
+
+    let window = create_window(&app, kind, &params.unwrap_or_default())?;
+    // Multi-instance windows are labeled per instance, so only singletons
+    // have a stable geometry record to restore
+    if kind.spec().singleton && !window.is_visible().unwrap_or(false) {
+        restore_geometry(&window)?;
+    }
+    let _ = window.unminimize();
+    window.show()?;
+    window.set_focus()?;
+    Ok(window.label().to_string())
+}
+
+/// List open windows
+#[tauri::command]
+pub fn list_windows(app: tauri::AppHandle) -> Vec<WindowInfo> {
+    app.webview_windows()
+        .values()
+        .map(|window| window_info(&app, window))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kind_from_label() {
+        assert_eq!(WindowKind::from_label("main"), Some(WindowKind::Main));
+        assert_eq!(WindowKind::from_label("help-3"), Some(WindowKind::Help));
+        assert_eq!(WindowKind::from_label("help-x"), None);
+        // Singletons have exactly one label
+        assert_eq!(WindowKind::from_label("settings-2"), None);
+        assert_eq!(WindowKind::from_label("unknown"), None);
+    }
+
+    #[test]
+    fn test_labels_per_instance() {
+        let registry = WindowRegistry::default();
+        let help = WindowKind::Help.spec();
+        assert_eq!(registry.next_label(&help), "help-1");
+        assert_eq!(registry.next_label(&help), "help-2");
+        assert_eq!(
+            registry.next_label(&WindowKind::Settings.spec()),
+            "settings"
+        );
+    }
+
+    #[test]
+    fn test_only_app_routes_can_be_loaded() {
+        assert!(validate_route("/lessons/42/slides").is_ok());
+        assert!(validate_route("https://example.com").is_err());
+        assert!(validate_route("//example.com").is_err());
+        assert!(validate_route("lessons").is_err());
+    }
+}
@@ -0,0 +1,281 @@
+//! Window State
+//! Per-window geometry keyed by monitor layout, captured from window events
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri::{Manager, WebviewWindow, Window, WindowEvent};
+
+use super::geometry::apply_window_config;
+use super::kiosk::kiosk_active;
+use crate::common::{app_data_file, read_yaml, write_yaml, AppResult};
+use crate::config::{load_config, ScreenMode, WindowConfig};
+
+/// Window geometry file name
+const WINDOW_STATE_FILE_NAME: &str = "window_state.yaml";
+
+/// Quiet period after the last move/resize before geometry is saved
+const SAVE_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Geometry saved for one window under one monitor layout
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedGeometry {
+    pub geometry: WindowConfig,
+    pub saved_at: DateTime<Utc>,
+}
+
+/// Saved geometry by window label, then by monitor layout fingerprint
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WindowStateStore {
+    pub windows: BTreeMap<String, BTreeMap<String, SavedGeometry>>,
+}
+
+impl WindowStateStore {
+    /// Geometry for a window under a layout, falling back to the most
+    /// recently saved geometry of that window under any layout
+    pub fn get(&self, label: &str, fingerprint: &str) -> Option<&WindowConfig> {
+        let layouts = self.windows.get(label)?;
+        layouts
+            .get(fingerprint)
+            .or_else(|| layouts.values().max_by_key(|saved| saved.saved_at))
+            .map(|saved| &saved.geometry)
+    }
+
+    pub fn set(&mut self, label: &str, fingerprint: &str, geometry: WindowConfig) {
+        self.windows.entry(label.to_string()).or_default().insert(
+            fingerprint.to_string(),
+            SavedGeometry {
+                geometry,
+                saved_at: Utc::now(),
+            },
+        );
+    }
+}
+
+/// Identify the connected monitor layout, e.g. "0,0 1920x1080;1920,0 1280x1024"
+pub fn monitor_fingerprint(app: &tauri::AppHandle) -> String {
+    let mut monitors: Vec<String> = app
+        .available_monitors()
+        .unwrap_or_default()
+        .iter()
+        .map(|m| {
+            format!(
+                "{},{} {}x{}",
+                m.position().x,
+                m.position().y,
+                m.size().width,
+                m.size().height
+            )
+        })
+        .collect();
+    monitors.sort();
+    monitors.join(";")
+}
+
+/// Load saved window geometry from disk
+pub fn load_window_states(app: &tauri::AppHandle) -> AppResult<WindowStateStore> {
+    let path = app_data_file(app, WINDOW_STATE_FILE_NAME)?;
+    Ok(read_yaml(&path)?.unwrap_or_default())
+}
+
+/// Save window geometry to disk
+pub fn save_window_states(app: &tauri::AppHandle, store: &WindowStateStore) -> AppResult<()> {
+    let path = app_data_file(app, WINDOW_STATE_FILE_NAME)?;
+    write_yaml(&path, store)
+}
+
+/// Saved geometry for a window under the current monitor layout
+///
+/// `main` falls back to the legacy single `window` record in `config.yaml`.
+pub fn saved_geometry(app: &tauri::AppHandle, label: &str) -> Option<WindowConfig> {
+    let store = load_window_states(app).unwrap_or_default();
+    match store.get(label, &monitor_fingerprint(app)) {
+        Some(geometry) => Some(geometry.clone()),
+        None if label == "main" => Some(load_config(app).window),
+        None => None,
+    }
+}
+
+/// Apply the saved geometry of a window that is not shown yet
+pub fn restore_geometry(window: &WebviewWindow) -> tauri::Result<()> {
+    match saved_geometry(window.app_handle(), window.label()) {
+        Some(geometry) => apply_window_config(window, &geometry),
+        None => Ok(()),
+    }
+}
+
+/// Read the current geometry of a window
+///
+/// While maximized or fullscreen only the screen mode is updated, so the
+/// normal size and position survive. Minimized windows are not captured.
+fn capture_geometry(window: &Window, previous: Option<&WindowConfig>) -> Option<WindowConfig> {
+    if window.is_minimized().unwrap_or(false) {
+        return None;
+    }
+
+    let screen_mode = if window.is_fullscreen().unwrap_or(false) {
+        ScreenMode::Fullscreen
+    } else if window.is_maximized().unwrap_or(false) {
+        ScreenMode::Maximized
+    } else {
+        ScreenMode::Normal
+    };
+
+    if screen_mode != ScreenMode::Normal {
+        if let Some(previous) = previous {
+            return Some(WindowConfig {
+                screen_mode,
+                ..previous.clone()
+            });
+        }
+    }
+
+    let size = window.outer_size().ok()?;
+    let position = window.outer_position().ok()?;
+    Some(WindowConfig {
+        width: size.width,
+        height: size.height,
+        x: Some(position.x),
+        y: Some(position.y),
+        screen_mode,
+    })
+}
+
+/// Capture and persist a window's geometry now
+///
+/// Skipped in kiosk mode, whose forced fullscreen must not replace the saved geometry.
+pub fn save_geometry(window: &Window) -> AppResult<()> {
+    let app = window.app_handle();
+    if kiosk_active(app) {
+        return Ok(());
+    }
+
+    let tracker = window.state::<GeometryTracker>();
+    let _guard = tracker.store_lock.lock().unwrap();
+
+    let fingerprint = monitor_fingerprint(app);
+    let mut store = load_window_states(app)?;
+
+    let previous = store.windows.get(window.label()).and_then(|layouts| {
+        layouts
+            .get(&fingerprint)
+            .map(|saved| saved.geometry.clone())
+    });
+    let Some(geometry) = capture_geometry(window, previous.as_ref()) else {
+        return Ok(());
+    };
+
+    store.set(window.label(), &fingerprint, geometry);
+    save_window_states(app, &store)
+}
+
+/// Persist geometry reported for a window under the current monitor layout
+pub fn store_geometry(
+    app: &tauri::AppHandle,
+    label: &str,
+    geometry: WindowConfig,
+) -> AppResult<()> {
+    if kiosk_active(app) {
+        return Ok(());
+    }
+
+    let tracker = app.state::<GeometryTracker>();
+    let _guard = tracker.store_lock.lock().unwrap();
+
+    let mut store = load_window_states(app)?;
+    store.set(label, &monitor_fingerprint(app), geometry);
+    save_window_states(app, &store)
+}
+
+/// Managed state debouncing geometry saves per window
+#[derive(Default)]
+pub struct GeometryTracker {
+    /// Save deadline per window label with a pending save
+    deadlines: Mutex<HashMap<String, Instant>>,
+    /// Serializes read-modify-write of the store across windows
+    store_lock: Mutex<()>,
+}
+
+impl GeometryTracker {
+    /// Push back the save deadline, starting a timer if none is pending
+    fn schedule(&self, window: &Window) {
+        let label = window.label().to_string();
+        let mut deadlines = self.deadlines.lock().unwrap();
+        let pending = deadlines
+            .insert(label.clone(), Instant::now() + SAVE_DEBOUNCE)
+            .is_some();
+        if pending {
+            return;
+        }
+
+        let window = window.clone();
+        thread::spawn(move || loop {
+            let tracker = window.state::<GeometryTracker>();
+            let deadline = tracker.deadlines.lock().unwrap().get(&label).copied();
+            let Some(deadline) = deadline else {
+                return;
+            };
+
+            let now = Instant::now();
+            if now < deadline {
+                thread::sleep(deadline - now);
+                continue;
+            }
+
+            tracker.deadlines.lock().unwrap().remove(&label);
+            let _ = save_geometry(&window);
+            return;
+        });
+    }
+
+    /// Drop any pending save, e.g. because the window is saved on close
+    fn cancel(&self, label: &str) {
+        self.deadlines.lock().unwrap().remove(label);
+    }
+}
+
+/// Record geometry changes from window events
+pub fn track_window_geometry(window: &Window, event: &WindowEvent) {
+    let tracker = window.state::<GeometryTracker>();
+    match event {
+        WindowEvent::Moved(_) | WindowEvent::Resized(_) => tracker.schedule(window),
+        WindowEvent::CloseRequested { .. } => {
+            tracker.cancel(window.label());
+            let _ = save_geometry(window);
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn geometry(width: u32) -> WindowConfig {
+        WindowConfig {
+            width,
+            ..WindowConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_lookup_prefers_layout_then_latest() {
+        let mut store = WindowStateStore::default();
+        store.set("main", "laptop", geometry(1280));
+        store.set("main", "docked", geometry(1900));
+        store
+            .windows
+            .get_mut("main")
+            .unwrap()
+            .get_mut("docked")
+            .unwrap()
+            .saved_at += chrono::Duration::seconds(1);
+
+        assert_eq!(store.get("main", "laptop").unwrap().width, 1280);
+        assert_eq!(store.get("main", "projector").unwrap().width, 1900);
+        assert!(store.get("auth", "laptop").is_none());
+    }
+}
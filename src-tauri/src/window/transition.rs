@@ -0,0 +1,207 @@
+//! Window Transitions
+//! Switches between the login and main windows as one step, rolled back on failure
+
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::{Emitter, Manager, WebviewWindow};
+
+use super::kiosk::{apply_kiosk, kiosk_active};
+use super::registry::{create_window, WindowKind, WindowParams};
+use super::state::restore_geometry;
+use crate::common::{error_language, AppResult};
+
+/// Event emitted as a transition starts, completes or is rolled back
+pub const WINDOW_TRANSITION_EVENT: &str = "window://transition";
+
+/// Which top-level window the user is in
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum WindowStage {
+    Auth,
+    Main,
+}
+
+impl WindowStage {
+    fn kind(self) -> WindowKind {
+        match self {
+            WindowStage::Auth => WindowKind::Auth,
+            WindowStage::Main => WindowKind::Main,
+        }
+    }
+
+    fn other(self) -> Self {
+        match self {
+            WindowStage::Auth => WindowStage::Main,
+            WindowStage::Main => WindowStage::Auth,
+        }
+    }
+}
+
+/// Progress of a transition
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TransitionPhase {
+    Started,
+    Completed,
+    /// A step failed and the previous window was restored
+    RolledBack,
+}
+
+/// Payload of `window://transition`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WindowTransition {
+    pub from: WindowStage,
+    pub to: WindowStage,
+    pub phase: TransitionPhase,
+    pub error: Option<String>,
+}
+
+/// Managed state serializing transitions
+#[derive(Default)]
+pub struct TransitionState {
+    lock: Mutex<()>,
+}
+
+/// Undo actions for the steps taken so far, run in reverse on failure
+#[derive(Default)]
+struct Rollback<'a> {
+    undo: Vec<Box<dyn FnOnce() + 'a>>,
+}
+
+impl<'a> Rollback<'a> {
+    fn push(&mut self, undo: impl FnOnce() + 'a) {
+        self.undo.push(Box::new(undo));
+    }
+
+    fn run(self) {
+        for undo in self.undo.into_iter().rev() {
+            undo();
+        }
+    }
+}
+
+fn emit_transition(
+    app: &tauri::AppHandle,
+    to: WindowStage,
+    phase: TransitionPhase,
+    error: Option<String>,
+) {
+    let _ = app.emit(
+        WINDOW_TRANSITION_EVENT,
+        WindowTransition {
+            from: to.other(),
+            to,
+            phase,
+            error,
+        },
+    );
+}
+
+fn is_visible(window: &WebviewWindow) -> bool {
+    window.is_visible().unwrap_or(false)
+}
+
+/// Show the target window and destroy the other one
+///
+/// Does nothing when the target is already the only window shown. If a step
+/// fails, a newly created target is destroyed, an existing one is hidden
+/// again, and the previous window is shown, so exactly one of the two
+/// windows stays open.
+pub fn transition_to(app: &tauri::AppHandle, to: WindowStage) -> AppResult<()> {
+    let transitions = app.state::<TransitionState>();
+    let _guard = transitions.lock.lock().unwrap();
+
+    let source_label = to.other().kind().spec().label;
+    let target_label = to.kind().spec().label;
+    let source = app.get_webview_window(source_label);
+    let existing = app.get_webview_window(target_label);
+
+    if source.is_none() && existing.as_ref().is_some_and(is_visible) {
+        return Ok(());
+    }
+
+    emit_transition(app, to, TransitionPhase::Started, None);
+    let mut rollback = Rollback::default();
+    match run_steps(app, to, source.as_ref(), existing, &mut rollback) {
+        Ok(()) => {
+            emit_transition(app, to, TransitionPhase::Completed, None);
+            Ok(())
+        }
+        Err(e) => {
+            log::error!("Transition to {:?} failed, rolling back: {}", to, e);
+            rollback.run();
+            if let Some(source) = &source {
+                let _ = source.show();
+                let _ = source.set_focus();
+            }
+            let message = e.localized_message(&error_language());
+            emit_transition(app, to, TransitionPhase::RolledBack, Some(message));
+            Err(e)
+        }
+    }
+}
+
+fn run_steps(
+    app: &tauri::AppHandle,
+    to: WindowStage,
+    source: Option<&WebviewWindow>,
+    existing: Option<WebviewWindow>,
+    rollback: &mut Rollback<'_>,
+) -> AppResult<()> {
+    let target = match existing {
+        Some(window) => {
+            if !is_visible(&window) {
+                restore_geometry(&window)?;
+                let hide = window.clone();
+                rollback.push(move || {
+                    let _ = hide.hide();
+                });
+            }
+            window
+        }
+        None => {
+            let window = create_window(app, to.kind(), &WindowParams::default())?;
+            let destroy = window.clone();
+            rollback.push(move || {
+                let _ = destroy.destroy();
+            });
+            restore_geometry(&window)?;
+            window
+        }
+    };
+
+    if to == WindowStage::Main && kiosk_active(app) {
+        apply_kiosk(&target, true)?;
+    }
+    target.show()?;
+    target.set_focus()?;
+
+    // Destroy rather than close, so close-to-tray does not just hide `main`
+    if let Some(source) = source {
+        source.destroy()?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    #[test]
+    fn test_rollback_runs_in_reverse() {
+        let log = RefCell::new(Vec::new());
+        let mut rollback = Rollback::default();
+        rollback.push(|| log.borrow_mut().push("destroy target"));
+        rollback.push(|| log.borrow_mut().push("hide target"));
+        rollback.run();
+
+        assert_eq!(*log.borrow(), vec!["hide target", "destroy target"]);
+    }
+
+    #[test]
+    fn test_stages_are_opposite() {
+        assert_eq!(WindowStage::Auth.other(), WindowStage::Main);
+        assert_eq!(WindowStage::Main.other().kind(), WindowKind::Auth);
+    }
+}
@@ -3,9 +3,23 @@
  * Central router configuration using feature-based routes
  */
 import { createHashRouter, Outlet } from "react-router-dom";
+import { getCurrentWindow } from "@tauri-apps/api/window";
 import { PageDepthProvider } from "../core/router";
-import { AuthLayout, MainLayout } from "../layouts";
-import { allAuthRoutes, allMainRoutes } from "../features";
+import {
+  AuthLayout,
+  MainLayout,
+  PresentationLayout,
+  WindowLayout,
+} from "../layouts";
+import {
+  allAuthRoutes,
+  allMainRoutes,
+  allPresentationRoutes,
+  allWindowRoutes,
+} from "../features";
+
+// The projector mirrors main routes without the app chrome
+const isPresentationWindow = getCurrentWindow().label === "presentation";
 
 export const router = createHashRouter([
   {
@@ -22,9 +36,19 @@ export const router = createHashRouter([
       },
       // Main app routes (dashboard, features, etc.)
       {
-        element: <MainLayout />,
+        element: isPresentationWindow ? <PresentationLayout /> : <MainLayout />,
         children: allMainRoutes,
       },
+      // Presentation window routes (blank screen, etc.)
+      {
+        element: <PresentationLayout />,
+        children: allPresentationRoutes,
+      },
+      // Detached window routes (settings, help)
+      {
+        element: <WindowLayout />,
+        children: allWindowRoutes,
+      },
     ],
   },
 ]);
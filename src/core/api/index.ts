@@ -13,6 +13,9 @@ export {
 // API Types
 export {
   ApiError,
+  isAppErrorPayload,
+  type AppErrorCode,
+  type AppErrorPayload,
   type ApiResponse,
   type PaginatedResponse,
   type ApiRequestOptions,
@@ -2,6 +2,98 @@
  * API Error Types
  */
 
+/**
+ * Stable error code (Rust `ErrorCode`); codes are never renamed or reused
+ */
+export type AppErrorCode =
+  | "CONFIG"
+  | "IO"
+  | "SERIALIZATION"
+  | "WINDOW"
+  | "AUTH"
+  | "PERMISSION"
+  | "NETWORK"
+  | "NOT_FOUND"
+  | "INVALID_INPUT"
+  | "UNKNOWN"
+  | "CONFIG_DIR_NOT_FOUND"
+  | "CONFIG_READ_FAILED"
+  | "CONFIG_WRITE_FAILED"
+  | "CONFIG_PARSE_FAILED"
+  | "CONFIG_INVALID_THEME"
+  | "CONFIG_INVALID_SCREEN_MODE"
+  | "APP_DATA_DIR_UNAVAILABLE"
+  | "KEYCHAIN_UNAVAILABLE"
+  | "FILE_PARSE_FAILED"
+  | "FILE_WRITE_FAILED"
+  | "ARCHIVE_WRITE_FAILED"
+  | "YAML_INVALID"
+  | "JSON_INVALID"
+  | "API_NOT_CONFIGURED"
+  | "API_UNREACHABLE"
+  | "API_INVALID_RESPONSE"
+  | "API_REQUEST_REJECTED"
+  | "API_UNAUTHORIZED"
+  | "API_FORBIDDEN"
+  | "API_NOT_FOUND"
+  | "API_SERVER_ERROR"
+  | "AUTH_INVALID_CREDENTIALS"
+  | "AUTH_TOKEN_EXPIRED"
+  | "AUTH_TOKEN_INVALID"
+  | "AUTH_USER_NOT_FOUND"
+  | "AUTH_USER_DISABLED"
+  | "AUTH_TOO_MANY_ATTEMPTS"
+  | "AUTH_ACCOUNT_LOCKED"
+  | "AUTH_PERMISSION_DENIED"
+  | "AUTH_MFA_REQUIRED"
+  | "AUTH_MFA_ENROLLMENT_REQUIRED"
+  | "AUTH_WEAK_PASSWORD"
+  | "AUTH_NETWORK_ERROR"
+  | "AUTH_UNKNOWN"
+  | "SAVED_ACCOUNT_NOT_FOUND"
+  | "DEVICE_ID_INVALID"
+  | "DEVICE_KEY_CORRUPT"
+  | "DEVICE_CHALLENGE_INVALID"
+  | "WINDOW_NOT_FOUND"
+  | "WINDOW_OPERATION_FAILED"
+  | "WINDOW_INVALID_ROUTE"
+  | "WINDOW_NOT_DETACHABLE"
+  | "KIOSK_ACTION_DISABLED"
+  | "NO_DISPLAY_AVAILABLE"
+  | "DISPLAY_NOT_CONNECTED"
+  | "PRESENTATION_NOT_OPEN"
+  | "SHORTCUT_UNKNOWN_ACTION"
+  | "SHORTCUT_INVALID"
+  | "SHORTCUT_CONFLICT"
+  | "LOG_INVALID_LEVEL"
+  | "LOG_DIR_UNAVAILABLE";
+
+/**
+ * Error returned by a failing Tauri command (Rust `AppError`)
+ * `message` is already localized to the app language; the full error is in
+ * the log under `correlation_id`
+ */
+export interface AppErrorPayload {
+  kind: string;
+  code: AppErrorCode;
+  message: string;
+  retryable: boolean;
+  retry_after_secs?: number;
+  /** Values filled into the message, e.g. `theme` for CONFIG_INVALID_THEME */
+  params?: Record<string, string>;
+  data?: Record<string, unknown>;
+  correlation_id: string;
+}
+
+export function isAppErrorPayload(error: unknown): error is AppErrorPayload {
+  return (
+    typeof error === "object" &&
+    error !== null &&
+    typeof (error as AppErrorPayload).code === "string" &&
+    typeof (error as AppErrorPayload).message === "string"
+  );
+}
+
 /**
  * API Error class with additional context
  */
@@ -26,6 +118,10 @@ export class ApiError extends Error {
     if (typeof error === "string") {
       return new ApiError(error, "TAURI_ERROR");
     }
+
+    if (isAppErrorPayload(error)) {
+      return new ApiError(error.message, error.code, undefined, error);
+    }
     
     return new ApiError("Unknown error occurred", "UNKNOWN_ERROR", undefined, error);
   }
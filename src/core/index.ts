@@ -9,6 +9,9 @@ export * from "./api";
 // Configuration
 export * from "./config";
 
+// Logging
+export * from "./logging";
+
 // Router utilities
 export * from "./router";
 
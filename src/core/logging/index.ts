@@ -0,0 +1,56 @@
+/**
+ * Core Logging
+ * Forwards frontend messages to the backend log files and exports support bundles
+ */
+import { invoke } from "@tauri-apps/api/core";
+
+export type LogLevel = "error" | "warn" | "info" | "debug" | "trace";
+
+/**
+ * Write a message to the app log, tagged with this window and an optional component
+ */
+export function log(level: LogLevel, message: string, target?: string): void {
+  invoke("log_frontend", { level, message, target }).catch(() => {
+    // The backend is unreachable; the console is all that is left
+  });
+}
+
+export const logger = {
+  error: (message: string, target?: string) => log("error", message, target),
+  warn: (message: string, target?: string) => log("warn", message, target),
+  info: (message: string, target?: string) => log("info", message, target),
+  debug: (message: string, target?: string) => log("debug", message, target),
+};
+
+function describe(reason: unknown): string {
+  if (reason instanceof Error) {
+    return reason.stack ?? `${reason.name}: ${reason.message}`;
+  }
+  return String(reason);
+}
+
+/**
+ * Log uncaught errors and unhandled promise rejections
+ */
+export function installLogForwarding(): void {
+  window.addEventListener("error", (event) => {
+    logger.error(describe(event.error ?? event.message), "uncaught");
+  });
+  window.addEventListener("unhandledrejection", (event) => {
+    logger.error(describe(event.reason), "unhandled_rejection");
+  });
+}
+
+/**
+ * Zip recent log files for support and return the archive path
+ */
+export async function exportLogBundle(days?: number): Promise<string> {
+  return invoke<string>("export_log_bundle", { days });
+}
+
+/**
+ * Zip a redacted diagnostics bundle for IT support and return the archive path
+ */
+export async function exportDiagnostics(): Promise<string> {
+  return invoke<string>("export_diagnostics");
+}
@@ -112,7 +112,7 @@ export default function LoginPage() {
             setError(t("auth.errorAccountInactive"));
             break;
           default:
-            setError(t("auth.errorLogin"));
+            setError(error.message || t("auth.errorLogin"));
         }
       } else {
         setError(t("auth.errorLogin"));
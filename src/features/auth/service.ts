@@ -9,7 +9,6 @@ import {
   AuthUser,
   SavedAccount,
   AuthErrorCode,
-  UserStatus,
 } from "./types";
 import {
   storeTokens,
@@ -21,14 +20,10 @@ import {
   AUTH_STORAGE_KEYS,
 } from "./storage";
 
+import { isAppErrorPayload, type AppErrorPayload } from "../../core/api";
+
 // Import mocks (will be replaced with real API later)
-import {
-  findUserByUsername,
-  findAuthUserByUsername,
-  validateMockPassword,
-  getMockSavedAccounts,
-  getPermissionStrings,
-} from "./mocks";
+import { getMockSavedAccounts, getPermissionStrings } from "./mocks";
 
 // ============================================================================
 // Error Class
@@ -156,97 +151,120 @@ export async function clearSavedAccounts(): Promise<void> {
 // ============================================================================
 
 /**
- * Login with username and password
- * Currently uses mock data - will be replaced with actual API call
+ * Backend login response (Rust `LoginResponse`)
  */
-export async function login(credentials: LoginRequest): Promise<LoginResponse> {
-  await mockDelay();
-
-  const { username, password, remember_me } = credentials;
-
-  // Find user
-  const user = findUserByUsername(username);
-
-  if (!user) {
-    throw new AuthError("User not found", AuthErrorCode.USER_NOT_FOUND);
-  }
-
-  // Check account status
-  if (user.status === UserStatus.LOCKED || user.is_locked) {
-    throw new AuthError("Account is locked", AuthErrorCode.ACCOUNT_LOCKED);
-  }
+interface BackendLoginResponse {
+  user: AuthUser;
+  access_token: string;
+  refresh_token?: string;
+  expires_in: number;
+}
 
-  if (user.status === UserStatus.INACTIVE) {
-    throw new AuthError("Account is inactive", AuthErrorCode.ACCOUNT_INACTIVE);
+/**
+ * Map a backend error code onto the auth error codes the login page handles
+ */
+function toAuthErrorCode(error: AppErrorPayload): AuthErrorCode {
+  switch (error.code) {
+    case "AUTH_INVALID_CREDENTIALS":
+      return AuthErrorCode.INVALID_CREDENTIALS;
+    case "AUTH_USER_NOT_FOUND":
+      return AuthErrorCode.USER_NOT_FOUND;
+    case "AUTH_ACCOUNT_LOCKED":
+    case "AUTH_TOO_MANY_ATTEMPTS":
+      return AuthErrorCode.ACCOUNT_LOCKED;
+    case "AUTH_USER_DISABLED":
+      return AuthErrorCode.ACCOUNT_INACTIVE;
+    case "AUTH_TOKEN_EXPIRED":
+      return AuthErrorCode.TOKEN_EXPIRED;
+    case "AUTH_TOKEN_INVALID":
+      return AuthErrorCode.TOKEN_INVALID;
+    case "AUTH_PERMISSION_DENIED":
+      return AuthErrorCode.PERMISSION_DENIED;
+    case "AUTH_NETWORK_ERROR":
+    case "API_UNREACHABLE":
+      return AuthErrorCode.NETWORK_ERROR;
+    default:
+      return AuthErrorCode.UNKNOWN_ERROR;
   }
+}
 
-  if (user.status === UserStatus.PENDING) {
-    throw new AuthError(
-      "Account is pending activation",
-      AuthErrorCode.ACCOUNT_INACTIVE
-    );
+/**
+ * Wrap a failed command in an AuthError, keeping the backend's localized message
+ */
+function toAuthError(error: unknown): AuthError {
+  if (isAppErrorPayload(error)) {
+    return new AuthError(error.message, toAuthErrorCode(error), error);
   }
+  return new AuthError("Login failed", AuthErrorCode.UNKNOWN_ERROR, error);
+}
 
-  // Validate password
-  if (!validateMockPassword(username, password)) {
-    throw new AuthError(
-      "Invalid credentials",
-      AuthErrorCode.INVALID_CREDENTIALS
-    );
-  }
+/**
+ * Login with username and password
+ * Signs in against the school API, then starts the backend session for that
+ * login; queued deep links are routed once the session starts
+ */
+export async function login(credentials: LoginRequest): Promise<LoginResponse> {
+  const { username, password, remember_me } = credentials;
 
-  // Get auth user data
-  const authUser = findAuthUserByUsername(username);
-  if (!authUser) {
-    throw new AuthError(
-      "Auth user data not found",
-      AuthErrorCode.UNKNOWN_ERROR
-    );
+  let response: BackendLoginResponse;
+  try {
+    response = await invoke<BackendLoginResponse>("login", {
+      request: { username, password, remember_me },
+    });
+    await invoke("start_session", {
+      request: {
+        user_id: response.user.user_id,
+        password,
+        card_uid: null,
+        refresh_expires_in: null,
+      },
+    });
+  } catch (error) {
+    throw toAuthError(error);
   }
 
-  // Generate tokens
-  const { accessToken, refreshToken } = generateMockTokens(user.user_id);
+  const authUser = response.user;
+  const refreshToken = response.refresh_token ?? "";
 
   // Store tokens and user
-  storeTokens(accessToken, refreshToken);
+  storeTokens(response.access_token, refreshToken);
   storeCurrentUser(authUser);
 
   // Save account for quick login if remember_me is true
   if (remember_me) {
     await saveAccount({
-      user_id: user.user_id,
-      username: user.username,
-      full_name: user.full_name,
-      avatar_url: user.avatar_url,
+      user_id: authUser.user_id,
+      username: authUser.username,
+      full_name: authUser.full_name,
+      avatar_url: authUser.avatar_url,
       role_type: authUser.role_type,
       last_login: new Date(),
     });
   }
 
   // Build response
-  const response: LoginResponse = {
+  return {
     success: true,
     message: "Login successful",
     data: {
-      access_token: accessToken,
+      access_token: response.access_token,
       refresh_token: refreshToken,
       token_type: "Bearer",
-      expires_in: 3600,
+      expires_in: response.expires_in,
       user: authUser,
       permissions: getPermissionStrings(authUser.permissions),
       roles: authUser.roles,
     },
   };
-
-  return response;
 }
 
 /**
  * Logout current user
  */
 export async function logout(): Promise<void> {
-  await mockDelay(200);
+  // Clear tokens first: the backend closes this window as part of logout
   clearTokens();
+  await invoke("logout_to_auth");
 }
 
 /**
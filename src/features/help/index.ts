@@ -0,0 +1,9 @@
+/**
+ * Help Feature Module
+ */
+
+// Routes
+export { helpRoutes } from "./routes";
+
+// Pages
+export * from "./pages";
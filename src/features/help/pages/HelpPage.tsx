@@ -0,0 +1,26 @@
+/**
+ * Help Page - Shown in detached help windows, optionally for one topic
+ */
+import { useTranslation } from "react-i18next";
+import { useParams } from "react-router-dom";
+
+export default function HelpPage() {
+  const { t } = useTranslation();
+  const { topic } = useParams();
+
+  return (
+    <div className="flex flex-col gap-2 h-full p-6">
+      <h1 className="text-2xl font-bold text-gray-900 dark:text-white">
+        {t("help.title")}
+      </h1>
+      {topic && (
+        <p className="text-sm text-gray-400 dark:text-gray-500 font-mono">
+          {t("help.topic", { topic })}
+        </p>
+      )}
+      <p className="text-gray-600 dark:text-gray-400">
+        {t("help.description")}
+      </p>
+    </div>
+  );
+}
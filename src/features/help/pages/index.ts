@@ -0,0 +1,4 @@
+/**
+ * Help Feature Pages
+ */
+export { default as HelpPage } from "./HelpPage";
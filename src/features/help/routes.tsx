@@ -0,0 +1,16 @@
+/**
+ * Help Feature Routes
+ */
+import { RouteObject } from "react-router-dom";
+import { HelpPage } from "./pages";
+
+/**
+ * Help window routes, e.g. "/help/nfc"
+ * These routes use the WindowLayout
+ */
+export const helpRoutes: RouteObject[] = [
+  { path: "/help", element: <HelpPage /> },
+  { path: "/help/:topic", element: <HelpPage /> },
+];
+
+export default helpRoutes;
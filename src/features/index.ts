@@ -7,6 +7,9 @@ import { RouteObject } from "react-router-dom";
 // Import feature routes
 import { authRoutes } from "./auth";
 import { dashboardRoutes } from "./dashboard";
+import { presentationRoutes } from "./presentation";
+import { settingsRoutes } from "./settings";
+import { helpRoutes } from "./help";
 
 /**
  * All auth-related routes (use AuthLayout)
@@ -18,8 +21,21 @@ export const allAuthRoutes: RouteObject[] = [...authRoutes];
  */
 export const allMainRoutes: RouteObject[] = [...dashboardRoutes];
 
+/**
+ * Presentation window routes (use PresentationLayout)
+ */
+export const allPresentationRoutes: RouteObject[] = [...presentationRoutes];
+
+/**
+ * Detached window routes (use WindowLayout)
+ */
+export const allWindowRoutes: RouteObject[] = [...settingsRoutes, ...helpRoutes];
+
 /**
  * Re-export features for direct access
  */
 export * from "./auth";
 export * from "./dashboard";
+export * from "./presentation";
+export * from "./settings";
+export * from "./help";
@@ -0,0 +1,9 @@
+/**
+ * Presentation Feature Module
+ */
+
+// Routes
+export { presentationRoutes } from "./routes";
+
+// Pages
+export * from "./pages";
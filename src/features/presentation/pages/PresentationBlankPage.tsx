@@ -0,0 +1,6 @@
+/**
+ * Presentation Blank Page - Empty screen shown on the projector
+ */
+export default function PresentationBlankPage() {
+  return <div className="w-full h-full bg-black" />;
+}
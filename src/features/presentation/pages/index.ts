@@ -0,0 +1,4 @@
+/**
+ * Presentation Feature Pages
+ */
+export { default as PresentationBlankPage } from "./PresentationBlankPage";
@@ -0,0 +1,16 @@
+/**
+ * Presentation Feature Routes
+ */
+import { RouteObject } from "react-router-dom";
+import { PresentationBlankPage } from "./pages";
+
+/**
+ * Presentation window routes
+ * These routes use the PresentationLayout
+ */
+export const presentationRoutes: RouteObject[] = [
+  // Shown when the presentation content is set to blank
+  { path: "/presentation/blank", element: <PresentationBlankPage /> },
+];
+
+export default presentationRoutes;
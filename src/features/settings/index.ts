@@ -0,0 +1,9 @@
+/**
+ * Settings Feature Module
+ */
+
+// Routes
+export { settingsRoutes } from "./routes";
+
+// Pages
+export * from "./pages";
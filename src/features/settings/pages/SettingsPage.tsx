@@ -0,0 +1,48 @@
+/**
+ * Settings Page - Shown in the detached settings window
+ */
+import { useTranslation } from "react-i18next";
+import { Card, CardBody } from "@heroui/react";
+import { ThemeSwitcher, LanguageSwitcher } from "../../../shared/components";
+
+export default function SettingsPage() {
+  const { t } = useTranslation();
+
+  const rows = [
+    {
+      key: "appearance",
+      label: t("settings.appearance"),
+      description: t("settings.appearanceDesc"),
+      control: <ThemeSwitcher />,
+    },
+    {
+      key: "language",
+      label: t("settings.language"),
+      description: t("settings.languageDesc"),
+      control: <LanguageSwitcher />,
+    },
+  ];
+
+  return (
+    <div className="flex flex-col gap-4 h-full p-6">
+      <h1 className="text-2xl font-bold text-gray-900 dark:text-white">
+        {t("settings.title")}
+      </h1>
+      {rows.map((row) => (
+        <Card key={row.key} shadow="sm">
+          <CardBody className="flex flex-row items-center justify-between gap-4">
+            <div>
+              <p className="font-medium text-gray-900 dark:text-white">
+                {row.label}
+              </p>
+              <p className="text-sm text-gray-500 dark:text-gray-400">
+                {row.description}
+              </p>
+            </div>
+            {row.control}
+          </CardBody>
+        </Card>
+      ))}
+    </div>
+  );
+}
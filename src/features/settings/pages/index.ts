@@ -0,0 +1,4 @@
+/**
+ * Settings Feature Pages
+ */
+export { default as SettingsPage } from "./SettingsPage";
@@ -0,0 +1,15 @@
+/**
+ * Settings Feature Routes
+ */
+import { RouteObject } from "react-router-dom";
+import { SettingsPage } from "./pages";
+
+/**
+ * Settings window routes
+ * These routes use the WindowLayout
+ */
+export const settingsRoutes: RouteObject[] = [
+  { path: "/settings", element: <SettingsPage /> },
+];
+
+export default settingsRoutes;
@@ -2,10 +2,23 @@
  * Auth Layout
  * Layout wrapper for authentication pages
  */
+import { useEffect } from "react";
+import { invoke } from "@tauri-apps/api/core";
 import { Image } from "@heroui/react";
 import { TitleBar, AnimatedOutlet } from "../shared/components";
+import { clearTokens } from "../features/auth";
+import { AuthUser } from "../features/auth/types";
 
 export default function AuthLayout() {
+  // Sessions can end from the tray or the idle timeout; drop any tokens left behind
+  useEffect(() => {
+    invoke<AuthUser | null>("get_current_user")
+      .then((user) => {
+        if (!user) clearTokens();
+      })
+      .catch(() => {});
+  }, []);
+
   return (
     <div className="flex flex-col h-screen bg-white dark:bg-gray-900">
       <TitleBar rootRoutes={["/"]} />
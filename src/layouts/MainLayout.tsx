@@ -4,7 +4,6 @@
  * Uses extracted sub-components for cleaner code
  */
 import { useState, useEffect } from "react";
-import { invoke } from "@tauri-apps/api/core";
 import { Navbar, NavbarBrand } from "@heroui/react";
 import {
   TitleBar,
@@ -13,9 +12,14 @@ import {
   UserMenu,
   UserInfo,
   FullscreenControl,
+  LockScreen,
 } from "../shared/components";
-import { useWindowStateSync } from "../shared/hooks";
-import { getStoredUser } from "../features/auth";
+import {
+  useDeepLinks,
+  useIdleActivity,
+  usePresentationSync,
+} from "../shared/hooks";
+import { getStoredUser, logout } from "../features/auth";
 import { AuthUser } from "../features/auth/types";
 import { getMenuItemsByRole, MenuItem } from "./navigation";
 
@@ -24,8 +28,14 @@ export default function MainLayout() {
   const [currentUser, setCurrentUser] = useState<AuthUser | null>(null);
   const [menuItems, setMenuItems] = useState<MenuItem[]>([]);
 
-  // Auto-sync window state to config file
-  useWindowStateSync();
+  // Follow smartschool:// links routed to this window
+  useDeepLinks();
+
+  // Mirror the main window's route onto the presentation window
+  usePresentationSync();
+
+  // Keep the idle monitor from locking while the user is working
+  useIdleActivity();
 
   // Load current user on mount and set menu items based on role
   useEffect(() => {
@@ -39,7 +49,7 @@ export default function MainLayout() {
   const handleLogout = async () => {
     setIsLoggingOut(true);
     try {
-      await invoke("logout_to_auth");
+      await logout();
     } catch (err) {
       console.error("Logout error:", err);
     } finally {
@@ -82,6 +92,9 @@ export default function MainLayout() {
       <main className="flex-1 overflow-auto">
         <AnimatedOutlet />
       </main>
+
+      {/* Shown over everything while the session is locked */}
+      <LockScreen user={currentUser} />
     </div>
   );
 }
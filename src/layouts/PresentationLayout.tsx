@@ -0,0 +1,17 @@
+/**
+ * Presentation Layout
+ * Full-screen layout for the presentation window, without any app chrome
+ */
+import { Outlet } from "react-router-dom";
+import { usePresentationSync } from "../shared/hooks";
+
+export default function PresentationLayout() {
+  // Show the content pushed by the backend: blank, a mirror of main, or a route
+  usePresentationSync();
+
+  return (
+    <div className="w-full h-screen overflow-hidden bg-gray-50 dark:bg-gray-900">
+      <Outlet />
+    </div>
+  );
+}
@@ -0,0 +1,28 @@
+/**
+ * Window Layout
+ * Layout wrapper for detached windows such as settings and help
+ */
+import { TitleBar, AnimatedOutlet, LockScreen } from "../shared/components";
+import { useIdleActivity } from "../shared/hooks";
+import { getStoredUser } from "../features/auth";
+
+export default function WindowLayout() {
+  // Input in any window keeps the session from locking
+  useIdleActivity();
+
+  return (
+    <div className="flex flex-col h-screen bg-gray-50 dark:bg-gray-900">
+      <TitleBar
+        showBack={false}
+        showThemeSwitcher={false}
+        showLanguageSwitcher={false}
+      />
+      <main className="flex-1 overflow-auto">
+        <AnimatedOutlet />
+      </main>
+
+      {/* Locked together with the main window */}
+      <LockScreen user={getStoredUser()} />
+    </div>
+  );
+}
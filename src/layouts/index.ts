@@ -3,4 +3,6 @@
  */
 export { default as AuthLayout } from "./AuthLayout";
 export { default as MainLayout } from "./MainLayout";
+export { default as PresentationLayout } from "./PresentationLayout";
+export { default as WindowLayout } from "./WindowLayout";
 export * from "./navigation";
@@ -4,8 +4,11 @@ import { RouterProvider } from "react-router-dom";
 import { HeroUIProvider } from "@heroui/react";
 import { router } from "./app/router";
 import "./core/i18n";
+import { installLogForwarding } from "./core/logging";
 import "./styles/globals.css";
 
+installLogForwarding();
+
 ReactDOM.createRoot(document.getElementById("root") as HTMLElement).render(
   <React.StrictMode>
     <HeroUIProvider>
@@ -0,0 +1,119 @@
+/**
+ * Lock Screen Component
+ * Covers the main window while the session is locked for inactivity
+ */
+import { useState, useEffect, FormEvent } from "react";
+import { useTranslation } from "react-i18next";
+import { Button, Input, Form } from "@heroui/react";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+import { isAppErrorPayload } from "../../../core/api";
+import type { AuthUser } from "../../../features/auth/types";
+
+type UnlockMethod = "password" | "pin";
+
+interface IdleStatus {
+  state: "locked" | "unlocked";
+}
+
+interface LockScreenProps {
+  user: AuthUser | null;
+}
+
+export function LockScreen({ user }: LockScreenProps) {
+  const { t } = useTranslation();
+  const [locked, setLocked] = useState(false);
+  const [method, setMethod] = useState<UnlockMethod>("password");
+  const [secret, setSecret] = useState("");
+  const [error, setError] = useState("");
+  const [isUnlocking, setIsUnlocking] = useState(false);
+
+  // Follow the backend lock state, starting from the current one
+  useEffect(() => {
+    const unlisteners: (() => void)[] = [];
+
+    const setupListeners = async () => {
+      unlisteners.push(
+        await listen("session://locked", () => setLocked(true)),
+        await listen("session://unlocked", () => setLocked(false))
+      );
+      const status = await invoke<IdleStatus>("get_idle_status");
+      setLocked(status.state === "locked");
+    };
+
+    setupListeners().catch((err) => console.error("Lock screen error:", err));
+
+    return () => {
+      unlisteners.forEach((unlisten) => unlisten());
+    };
+  }, []);
+
+  if (!locked) return null;
+
+  const handleUnlock = async (e: FormEvent<HTMLFormElement>) => {
+    e.preventDefault();
+    setIsUnlocking(true);
+    setError("");
+
+    try {
+      await invoke("unlock_session", { method, secret });
+      setSecret("");
+      setLocked(false);
+    } catch (err) {
+      setError(isAppErrorPayload(err) ? err.message : t("lock.unlockFailed"));
+    } finally {
+      setIsUnlocking(false);
+    }
+  };
+
+  const switchMethod = () => {
+    setMethod(method === "password" ? "pin" : "password");
+    setSecret("");
+    setError("");
+  };
+
+  return (
+    <div className="fixed inset-0 z-50 flex items-center justify-center bg-gray-900/90 backdrop-blur-sm">
+      <Form
+        onSubmit={handleUnlock}
+        className="flex flex-col gap-3 w-full max-w-sm p-6 rounded-xl bg-white dark:bg-gray-800"
+      >
+        <h2 className="text-xl font-bold text-gray-800 dark:text-gray-200">
+          {t("lock.title")}
+        </h2>
+        <p className="text-sm text-gray-500 dark:text-gray-400">
+          {t("lock.description", {
+            name: user?.full_name || user?.username || "",
+          })}
+        </p>
+        <Input
+          type="password"
+          label={method === "password" ? t("auth.password") : t("lock.pin")}
+          inputMode={method === "pin" ? "numeric" : undefined}
+          value={secret}
+          onValueChange={(value) => {
+            setSecret(value);
+            if (error) setError("");
+          }}
+          isInvalid={!!error}
+          errorMessage={error}
+          autoFocus
+        />
+        <Button
+          type="submit"
+          color="primary"
+          className="w-full"
+          isLoading={isUnlocking}
+          isDisabled={!secret}
+        >
+          {t("lock.unlock")}
+        </Button>
+        <Button variant="light" size="sm" onPress={switchMethod}>
+          {method === "password" ? t("lock.usePin") : t("lock.usePassword")}
+        </Button>
+      </Form>
+    </div>
+  );
+}
+
+export default LockScreen;
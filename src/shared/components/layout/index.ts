@@ -13,3 +13,4 @@ export { NavMenu } from "./NavMenu";
 export { UserMenu } from "./UserMenu";
 export { UserInfo } from "./UserInfo";
 export { FullscreenControl } from "./FullscreenControl";
+export { LockScreen } from "./LockScreen";
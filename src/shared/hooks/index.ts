@@ -195,5 +195,11 @@ export function useKeyPress(
   }, [targetKey, callback, options]);
 }
 
-// Window state sync hook
-export { useWindowStateSync } from "./useWindowStateSync";
+// Deep link navigation hook
+export { useDeepLinks } from "./useDeepLinks";
+
+// Idle activity reporting hook
+export { useIdleActivity } from "./useIdleActivity";
+
+// Presentation window route sync hook
+export { usePresentationSync } from "./usePresentationSync";
@@ -0,0 +1,37 @@
+/**
+ * Deep Links Hook
+ * Navigates the main window to smartschool:// links routed by the backend
+ */
+import { useEffect } from "react";
+import { useNavigate } from "react-router-dom";
+import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
+
+/**
+ * Hook to follow deep links in the main window
+ * Takes links that arrived before sign-in on mount, then listens for new ones
+ */
+export function useDeepLinks() {
+  const navigate = useNavigate();
+
+  useEffect(() => {
+    let unlisten: (() => void) | undefined;
+
+    const setupListener = async () => {
+      const pending = await invoke<string[]>("take_pending_deep_links");
+      if (pending.length > 0) {
+        navigate(pending[pending.length - 1]);
+      }
+
+      unlisten = await listen<string>("deep-link://navigate", (event) => {
+        navigate(event.payload);
+      });
+    };
+
+    setupListener().catch((err) => console.error("Deep link error:", err));
+
+    return () => {
+      if (unlisten) unlisten();
+    };
+  }, [navigate]);
+}
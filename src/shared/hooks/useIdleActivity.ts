@@ -0,0 +1,46 @@
+/**
+ * Idle Activity Hook
+ * Reports user input to the backend idle monitor
+ */
+import { useEffect } from "react";
+import { invoke } from "@tauri-apps/api/core";
+
+/** Minimum time between two reports; well below the shortest idle timeout */
+const REPORT_INTERVAL_MS = 15_000;
+
+const ACTIVITY_EVENTS = [
+  "mousemove",
+  "mousedown",
+  "keydown",
+  "wheel",
+  "touchstart",
+] as const;
+
+/**
+ * Hook to report keyboard, mouse and touch input, throttled
+ * Without these reports the backend only locks where the OS reports idle time
+ */
+export function useIdleActivity() {
+  useEffect(() => {
+    let lastReport = 0;
+
+    const report = () => {
+      const now = Date.now();
+      if (now - lastReport < REPORT_INTERVAL_MS) return;
+      lastReport = now;
+
+      invoke("report_activity").catch((err) =>
+        console.error("Activity report error:", err)
+      );
+    };
+
+    ACTIVITY_EVENTS.forEach((name) =>
+      window.addEventListener(name, report, { passive: true })
+    );
+    return () => {
+      ACTIVITY_EVENTS.forEach((name) =>
+        window.removeEventListener(name, report)
+      );
+    };
+  }, []);
+}
@@ -0,0 +1,50 @@
+/**
+ * Presentation Sync Hook
+ * Keeps the presentation window in step with the main window's route
+ */
+import { useEffect } from "react";
+import { useLocation, useNavigate } from "react-router-dom";
+import { invoke } from "@tauri-apps/api/core";
+import { getCurrentWindow } from "@tauri-apps/api/window";
+
+/**
+ * Hook for the main and presentation layouts
+ * `main` reports its route so mirror mode can follow it, and `presentation`
+ * navigates to the routes pushed by the backend
+ */
+export function usePresentationSync() {
+  const navigate = useNavigate();
+  const location = useLocation();
+  const label = getCurrentWindow().label;
+  const route = location.pathname + location.search;
+
+  useEffect(() => {
+    if (label !== "main") return;
+
+    invoke("report_main_route", { route }).catch((err) =>
+      console.error("Route report error:", err)
+    );
+  }, [label, route]);
+
+  useEffect(() => {
+    if (label !== "presentation") return;
+    let unlisten: (() => void) | undefined;
+
+    const setupListener = async () => {
+      unlisten = await getCurrentWindow().listen<string>(
+        "presentation://navigate",
+        (event) => {
+          navigate(event.payload);
+        }
+      );
+    };
+
+    setupListener().catch((err) =>
+      console.error("Presentation sync error:", err)
+    );
+
+    return () => {
+      if (unlisten) unlisten();
+    };
+  }, [label, navigate]);
+}